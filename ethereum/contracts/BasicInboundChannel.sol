@@ -20,6 +20,7 @@ contract BasicInboundChannel {
         uint64 id;
         address target;
         bytes payload;
+        uint32 commitmentIndex;
     }
 
     event MessageDispatched(uint64 id, bool result);
@@ -46,8 +47,10 @@ contract BasicInboundChannel {
     function processMessages(MessageBundle calldata bundle) internal {
         require(bundle.nonce == nonce + 1, "invalid nonce");
 
+        // Heartbeat bundles have no messages and only advance the nonce.
         for (uint256 i = 0; i < bundle.messages.length; i++) {
             Message calldata message = bundle.messages[i];
+            require(message.commitmentIndex == i, "invalid message order");
 
             // Deliver the message to the target
             (bool success, ) = message.target.call{ value: 0, gas: MAX_GAS_PER_MESSAGE }(
@@ -0,0 +1,30 @@
+//! Runtime API for the basic outbound channel, used by relayers to locate committed
+//! message bundles in offchain storage.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_core::H256;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait BasicOutboundChannelApi {
+		/// Offchain storage key under which the message bundle for the commitment
+		/// `hash` was persisted.
+		fn offchain_key(hash: H256) -> Vec<u8>;
+
+		/// Commitment hash of the message bundle committed with `nonce`, if it is among the
+		/// most recently committed ones.
+		fn commitment_hash_by_nonce(nonce: u64) -> Option<H256>;
+
+		/// Whether a message bundle with `nonce` has been committed.
+		fn is_nonce_committed(nonce: u64) -> bool;
+
+		/// Commitment hash of a SCALE-encoded message bundle fetched from offchain storage, or
+		/// `None` if it doesn't decode.
+		fn commitment_hash_of_encoded_bundle(encoded: Vec<u8>) -> Option<H256>;
+
+		/// Whether `encoded` matches the message bundle persisted offchain for `nonce`. A
+		/// mismatch means the offchain copy is corrupted.
+		fn is_offchain_bundle_intact(nonce: u64, encoded: Vec<u8>) -> bool;
+	}
+}
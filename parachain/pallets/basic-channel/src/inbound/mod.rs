@@ -9,7 +9,10 @@ pub mod weights;
 mod test;
 
 use frame_system::ensure_signed;
-use snowbridge_core::{ChannelId, Message, MessageDispatch, MessageId, Verifier};
+use snowbridge_core::{
+	BeaconProof, BeaconVerifier, ChannelId, Message, MessageDispatch, MessageId, Verifier,
+};
+use snowbridge_ethereum::Log;
 use sp_core::H160;
 use sp_std::convert::TryFrom;
 
@@ -37,6 +40,9 @@ pub mod pallet {
 		/// Verifier module for message verification.
 		type Verifier: Verifier;
 
+		/// Verifier module for messages proven against finalized beacon chain headers.
+		type BeaconVerifier: BeaconVerifier;
+
 		/// Verifier module for message verification.
 		type MessageDispatch: MessageDispatch<Self, MessageId>;
 
@@ -58,6 +64,8 @@ pub mod pallet {
 		InvalidEnvelope,
 		/// Message has an unexpected nonce.
 		InvalidNonce,
+		/// Message with this nonce has already been processed.
+		AlreadyProcessed,
 	}
 
 	/// Source channel on the ethereum side
@@ -94,7 +102,24 @@ pub mod pallet {
 			ensure_signed(origin)?;
 			// submit message to verifier for verification
 			let log = T::Verifier::verify(&message)?;
+			Self::process_log(log)
+		}
+
+		#[pallet::weight(100_000_000)]
+		pub fn submit_with_beacon_proof(
+			origin: OriginFor<T>,
+			message: Message,
+			beacon_proof: BeaconProof,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			// verify the message against a finalized beacon chain header
+			let log = T::BeaconVerifier::verify(&message, &beacon_proof)?;
+			Self::process_log(log)
+		}
+	}
 
+	impl<T: Config> Pallet<T> {
+		fn process_log(log: Log) -> DispatchResult {
 			// Decode log into an Envelope
 			let envelope = Envelope::try_from(log).map_err(|_| Error::<T>::InvalidEnvelope)?;
 
@@ -104,9 +129,12 @@ pub mod pallet {
 				return Err(Error::<T>::InvalidSourceChannel.into())
 			}
 
-			// Verify message nonce
+			// Verify message nonce. Nonces are processed strictly in order, so every nonce up to
+			// the current one has already been processed.
 			<Nonce<T>>::try_mutate(|nonce| -> DispatchResult {
-				if envelope.nonce != *nonce + 1 {
+				if envelope.nonce <= *nonce {
+					Err(Error::<T>::AlreadyProcessed.into())
+				} else if envelope.nonce != *nonce + 1 {
 					Err(Error::<T>::InvalidNonce.into())
 				} else {
 					*nonce += 1;
@@ -119,5 +147,10 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Nonce of the most recently processed message.
+		pub fn highest_processed_nonce() -> u64 {
+			<Nonce<T>>::get()
+		}
 	}
 }
@@ -15,7 +15,7 @@ use sp_runtime::{
 };
 use sp_std::convert::From;
 
-use snowbridge_core::{Message, MessageDispatch, Proof};
+use snowbridge_core::{BeaconProof, Message, MessageDispatch, Proof};
 use snowbridge_ethereum::{Header as EthereumHeader, Log, U256};
 
 use hex_literal::hex;
@@ -83,6 +83,19 @@ impl Verifier for MockVerifier {
 	}
 }
 
+// Mock beacon verifier, accepting any proof with a non-empty execution branch
+pub struct MockBeaconVerifier;
+
+impl BeaconVerifier for MockBeaconVerifier {
+	fn verify(message: &Message, beacon_proof: &BeaconProof) -> Result<Log, DispatchError> {
+		if beacon_proof.execution_branch.is_empty() {
+			return Err(DispatchError::Other("Invalid proof"))
+		}
+		let log: Log = rlp::decode(&message.data).unwrap();
+		Ok(log)
+	}
+}
+
 // Mock Dispatch
 pub struct MockMessageDispatch;
 
@@ -98,6 +111,7 @@ impl MessageDispatch<Test, MessageId> for MockMessageDispatch {
 impl basic_inbound_channel::Config for Test {
 	type Event = Event;
 	type Verifier = MockVerifier;
+	type BeaconVerifier = MockBeaconVerifier;
 	type MessageDispatch = MockMessageDispatch;
 	type WeightInfo = ();
 }
@@ -203,6 +217,7 @@ fn test_submit() {
 		assert_ok!(BasicInboundChannel::submit(origin.clone(), message_1));
 		let nonce: u64 = <Nonce<Test>>::get();
 		assert_eq!(nonce, 1);
+		assert_eq!(BasicInboundChannel::highest_processed_nonce(), 1);
 
 		// Submit message 2
 		let message_2 = Message {
@@ -216,11 +231,34 @@ fn test_submit() {
 		assert_ok!(BasicInboundChannel::submit(origin.clone(), message_2));
 		let nonce: u64 = <Nonce<Test>>::get();
 		assert_eq!(nonce, 2);
+		assert_eq!(BasicInboundChannel::highest_processed_nonce(), 2);
 	});
 }
 
 #[test]
 fn test_submit_with_invalid_nonce() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer);
+
+		// Submit message with nonce 2 before nonce 1
+		let message = Message {
+			data: MESSAGE_DATA_1.into(),
+			proof: Proof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			},
+		};
+		assert_noop!(
+			BasicInboundChannel::submit(origin.clone(), message.clone()),
+			Error::<Test>::InvalidNonce
+		);
+	});
+}
+
+#[test]
+fn test_submit_already_processed() {
 	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
 		let relayer: AccountId = Keyring::Bob.into();
 		let origin = Origin::signed(relayer);
@@ -241,7 +279,51 @@ fn test_submit_with_invalid_nonce() {
 		// Submit the same again
 		assert_noop!(
 			BasicInboundChannel::submit(origin.clone(), message.clone()),
-			Error::<Test>::InvalidNonce
+			Error::<Test>::AlreadyProcessed
+		);
+	});
+}
+
+#[test]
+fn test_submit_with_beacon_proof() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer);
+
+		let message = Message {
+			data: MESSAGE_DATA_0.into(),
+			proof: Proof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			},
+		};
+		let beacon_proof = BeaconProof {
+			slot: 1,
+			execution_header: Default::default(),
+			execution_branch: vec![H256::repeat_byte(1)],
+		};
+
+		assert_noop!(
+			BasicInboundChannel::submit_with_beacon_proof(
+				origin.clone(),
+				message.clone(),
+				BeaconProof { execution_branch: vec![], ..beacon_proof.clone() }
+			),
+			DispatchError::Other("Invalid proof")
+		);
+
+		assert_ok!(BasicInboundChannel::submit_with_beacon_proof(
+			origin.clone(),
+			message.clone(),
+			beacon_proof.clone()
+		));
+		assert_eq!(BasicInboundChannel::highest_processed_nonce(), 1);
+
+		// Nonces are shared with messages verified by the execution light client
+		assert_noop!(
+			BasicInboundChannel::submit(origin.clone(), message.clone()),
+			Error::<Test>::AlreadyProcessed
 		);
 	});
 }
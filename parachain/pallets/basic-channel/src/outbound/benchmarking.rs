@@ -21,7 +21,7 @@ benchmarks! {
 			<MessageQueue<T>>::try_append(Message {
 				id: 0u64,
 				target: H160::zero(),
-				payload,
+				payload: payload.try_into().unwrap(),
 			}).unwrap();
 		}
 
@@ -38,7 +38,7 @@ benchmarks! {
 		<MessageQueue<T>>::try_append(Message {
 			id: 0u64,
 			target: H160::zero(),
-			payload: vec![1u8; T::MaxMessagePayloadSize::get() as usize],
+			payload: vec![1u8; T::MaxMessagePayloadSize::get() as usize].try_into().unwrap(),
 		}).unwrap();
 
 		Interval::<T>::put::<T::BlockNumber>(10u32.into());
@@ -68,6 +68,29 @@ benchmarks! {
 	verify {
 		assert_eq!(<Principal<T>>::get(), Some(account("alice", 0, SEED)));
 	}
+
+	add_target {
+		let authorized_origin = match T::SetPrincipalOrigin::successful_origin().into() {
+			Ok(raw) => raw,
+			Err(_) => return Err(BenchmarkError::Stop("Failed to get raw origin from origin")),
+		};
+		let target = H160::repeat_byte(1);
+	}: _(authorized_origin, target)
+	verify {
+		assert!(<AllowedTargets<T>>::contains_key(target));
+	}
+
+	remove_target {
+		let authorized_origin = match T::SetPrincipalOrigin::successful_origin().into() {
+			Ok(raw) => raw,
+			Err(_) => return Err(BenchmarkError::Stop("Failed to get raw origin from origin")),
+		};
+		let target = H160::repeat_byte(1);
+		<AllowedTargets<T>>::insert(target, ());
+	}: _(authorized_origin, target)
+	verify {
+		assert!(!<AllowedTargets<T>>::contains_key(target));
+	}
 }
 
 impl_benchmark_test_suite!(
@@ -6,17 +6,21 @@ mod benchmarking;
 #[cfg(test)]
 mod test;
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use ethabi::{self, Token};
 use frame_support::{
 	dispatch::DispatchResult,
 	ensure,
 	traits::{EnsureOrigin, Get},
+	BoundedVec, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use scale_info::TypeInfo;
-use sp_core::{RuntimeDebug, H160, H256};
+use sp_core::{H160, H256};
 use sp_io::offchain_index;
-use sp_runtime::traits::{Hash, StaticLookup, Zero};
+use sp_runtime::{
+	traits::{Hash, StaticLookup, Zero},
+	Perbill,
+};
 
 use sp_std::prelude::*;
 
@@ -24,21 +28,48 @@ use snowbridge_core::{types::AuxiliaryDigestItem, ChannelId};
 
 pub use weights::WeightInfo;
 
+/// Version of the offchain key scheme and of the [`MessageBundle`] wire-format.
+///
+/// Must be bumped whenever either of them changes, so that relayers can distinguish
+/// entries written before and after a format migration.
+pub const COMMITMENT_VERSION: u8 = 1;
+
 /// Wire-format for committed messages
-#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
-pub struct MessageBundle {
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo)]
+#[scale_info(skip_type_params(M))]
+pub struct MessageBundle<M: Get<u32>> {
+	version: u8,
 	nonce: u64,
-	messages: Vec<Message>,
+	messages: Vec<CommittedMessage<M>>,
 }
 
-#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
-pub struct Message {
+/// A message as committed in a [`MessageBundle`].
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo)]
+#[scale_info(skip_type_params(M))]
+pub struct CommittedMessage<M: Get<u32>> {
 	/// Unique message ID
 	id: u64,
 	/// Target application on the Ethereum side.
 	target: H160,
 	/// Payload for target application.
-	payload: Vec<u8>,
+	payload: BoundedVec<u8, M>,
+	/// Position of the message within its bundle.
+	commitment_index: u32,
+}
+
+/// A queued message, with its payload bounded by `M` bytes.
+#[derive(
+	Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(M))]
+#[codec(mel_bound())]
+pub struct Message<M: Get<u32>> {
+	/// Unique message ID
+	id: u64,
+	/// Target application on the Ethereum side.
+	target: H160,
+	/// Payload for target application.
+	payload: BoundedVec<u8, M>,
 }
 
 pub use pallet::*;
@@ -53,7 +84,6 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
-	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -67,12 +97,32 @@ pub mod pallet {
 
 		/// Max bytes in a message payload
 		#[pallet::constant]
-		type MaxMessagePayloadSize: Get<u64>;
+		type MaxMessagePayloadSize: Get<u32>;
 
 		/// Max number of messages per commitment
 		#[pallet::constant]
 		type MaxMessagesPerCommit: Get<u32>;
 
+		/// Fraction of [`Config::MaxMessagesPerCommit`] at which the queue is committed without
+		/// waiting for the next interval. Zero disables early commitments.
+		#[pallet::constant]
+		type CommitThreshold: Get<Perbill>;
+
+		/// Every this many blocks a commitment is made even if the queue is empty, so that
+		/// Ethereum can tell an idle channel from a stalled one. Zero disables heartbeats.
+		#[pallet::constant]
+		type HeartbeatInterval: Get<Self::BlockNumber>;
+
+		/// Whether to reject a message whose target and payload match a message already queued
+		/// in the current commit cycle.
+		#[pallet::constant]
+		type DeduplicateMessages: Get<bool>;
+
+		/// Number of most recent nonces for which the commitment hash is kept. Older entries
+		/// are pruned as new bundles are committed, and none are kept if this is zero.
+		#[pallet::constant]
+		type CommitmentHistorySize: Get<u64>;
+
 		type SetPrincipalOrigin: EnsureOrigin<Self::Origin>;
 
 		/// Weight information for extrinsics in this pallet
@@ -83,6 +133,14 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		MessageAccepted(u64),
+		/// A message bundle was committed, with the figures its weight was charged for.
+		Committed { nonce: u64, message_count: u32, average_payload_size: u32 },
+		/// The account allowed to submit messages was changed.
+		PrincipalChanged { old: Option<T::AccountId>, new: T::AccountId },
+		/// An account was proposed as the next principal, pending its acceptance.
+		PrincipalProposed { proposed: T::AccountId },
+		/// The proposed account accepted and became the principal.
+		PrincipalAccepted { old: Option<T::AccountId>, new: T::AccountId },
 	}
 
 	#[pallet::error]
@@ -95,6 +153,14 @@ pub mod pallet {
 		Overflow,
 		/// Not authorized to send message
 		NotAuthorized,
+		/// Message target is not in the set of allowed targets.
+		TargetNotAllowed,
+		/// An identical message is already queued in this commit cycle.
+		DuplicateMessage,
+		/// The caller is not the proposed principal.
+		NotProposedPrincipal,
+		/// A commitment was already made in this block. Relayers expect at most one per block.
+		AlreadyCommitted,
 	}
 
 	/// Interval between commitments
@@ -105,19 +171,55 @@ pub mod pallet {
 	/// Messages waiting to be committed.
 	#[pallet::storage]
 	pub(super) type MessageQueue<T: Config> =
-		StorageValue<_, BoundedVec<Message, T::MaxMessagesPerCommit>, ValueQuery>;
+		StorageValue<
+			_,
+			BoundedVec<Message<T::MaxMessagePayloadSize>, T::MaxMessagesPerCommit>,
+			ValueQuery,
+		>;
 
 	/// Fee for accepting a message
 	#[pallet::storage]
 	#[pallet::getter(fn principal)]
 	pub type Principal<T: Config> = StorageValue<_, Option<T::AccountId>, ValueQuery>;
 
+	/// Account proposed to become the principal, which it does once it accepts.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_principal)]
+	pub type PendingPrincipal<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// Targets which messages may be sent to. When empty, any target is allowed.
+	#[pallet::storage]
+	pub type AllowedTargets<T: Config> = StorageMap<_, Twox64Concat, H160, (), OptionQuery>;
+
+	/// Hashes of the messages queued since the last commitment, when
+	/// [`Config::DeduplicateMessages`] is enabled.
+	#[pallet::storage]
+	pub(super) type PendingMessageHashes<T: Config> =
+		StorageValue<_, BoundedVec<H256, T::MaxMessagesPerCommit>, ValueQuery>;
+
 	#[pallet::storage]
 	pub type Nonce<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+	/// Commitment hash of each of the last [`Config::CommitmentHistorySize`] committed message
+	/// bundles, keyed by the bundle's nonce.
+	#[pallet::storage]
+	pub type NonceToCommitmentHash<T: Config> =
+		StorageMap<_, Twox64Concat, u64, H256, OptionQuery>;
+
+	/// Hash of the SCALE-encoded message bundle written to offchain storage, keyed by the
+	/// bundle's nonce. Lets relayers tell a corrupted offchain bundle from a missing one. Pruned
+	/// along with [`NonceToCommitmentHash`].
+	#[pallet::storage]
+	pub type NonceToBundleChecksum<T: Config> =
+		StorageMap<_, Twox64Concat, u64, H256, OptionQuery>;
+
 	#[pallet::storage]
 	pub type NextId<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+	/// Block in which the latest commitment was made.
+	#[pallet::storage]
+	pub(super) type LastCommitBlock<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub interval: T::BlockNumber,
@@ -141,15 +243,21 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		// Generate a message commitment every [`Interval`] blocks.
+		// Generate a message commitment every [`Interval`] blocks, or earlier once the queue
+		// fills up to [`Config::CommitThreshold`]. An empty commitment is generated every
+		// [`Config::HeartbeatInterval`] blocks.
 		//
 		// The commitment hash is included in an [`AuxiliaryDigestItem`] in the block header,
 		// with the corresponding commitment is persisted offchain.
 		fn on_initialize(now: T::BlockNumber) -> Weight {
-			if (now % Self::interval()).is_zero() {
-				Self::commit()
+			if (now % Self::interval()).is_zero() ||
+				Self::queue_reached_threshold() ||
+				Self::is_heartbeat(now)
+			{
+				Self::commit(now)
 			} else {
 				T::WeightInfo::on_initialize_non_interval()
+					.saturating_add(T::DbWeight::get().reads(1))
 			}
 		}
 	}
@@ -163,7 +271,72 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::SetPrincipalOrigin::ensure_origin(origin)?;
 			let principal = T::Lookup::lookup(principal)?;
-			<Principal<T>>::put(Some(principal));
+			let old = <Principal<T>>::get();
+			<Principal<T>>::put(Some(principal.clone()));
+			// A handover proposed before governance stepped in must not override it.
+			<PendingPrincipal<T>>::kill();
+			Self::deposit_event(Event::PrincipalChanged { old, new: principal });
+			Ok(())
+		}
+
+		/// Proposes `principal` as the next principal. The current principal keeps submitting
+		/// until the proposed account confirms with `accept_principal`, so a mistyped or
+		/// unusable account can't lock out submissions.
+		#[pallet::weight(T::WeightInfo::set_principal())]
+		pub fn propose_principal(
+			origin: OriginFor<T>,
+			principal: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::SetPrincipalOrigin::ensure_origin(origin)?;
+			let principal = T::Lookup::lookup(principal)?;
+			<PendingPrincipal<T>>::put(principal.clone());
+			Self::deposit_event(Event::PrincipalProposed { proposed: principal });
+			Ok(())
+		}
+
+		/// Completes a handover started by `propose_principal`. Must be called by the proposed
+		/// account.
+		#[pallet::weight(T::WeightInfo::set_principal())]
+		pub fn accept_principal(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				<PendingPrincipal<T>>::get().as_ref() == Some(&who),
+				Error::<T>::NotProposedPrincipal
+			);
+			<PendingPrincipal<T>>::kill();
+			let old = <Principal<T>>::get();
+			<Principal<T>>::put(Some(who.clone()));
+			Self::deposit_event(Event::PrincipalAccepted { old, new: who });
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::add_target())]
+		pub fn add_target(origin: OriginFor<T>, target: H160) -> DispatchResult {
+			T::SetPrincipalOrigin::ensure_origin(origin)?;
+			<AllowedTargets<T>>::insert(target, ());
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::remove_target())]
+		pub fn remove_target(origin: OriginFor<T>, target: H160) -> DispatchResult {
+			T::SetPrincipalOrigin::ensure_origin(origin)?;
+			<AllowedTargets<T>>::remove(target);
+			Ok(())
+		}
+
+		/// Commit any queued messages immediately rather than waiting for the next interval,
+		/// e.g. ahead of a runtime upgrade or planned maintenance. Fails if a commitment was
+		/// already made in this block.
+		#[pallet::weight(T::WeightInfo::on_initialize(
+			T::MaxMessagesPerCommit::get(),
+			T::MaxMessagePayloadSize::get(),
+		))]
+		pub fn force_commit(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::principal() == Some(who), Error::<T>::NotAuthorized);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(<LastCommitBlock<T>>::get() != Some(now), Error::<T>::AlreadyCommitted);
+			Self::commit(now);
 			Ok(())
 		}
 	}
@@ -174,26 +347,31 @@ pub mod pallet {
 			let principal = Self::principal();
 			ensure!(principal.is_some(), Error::<T>::NotAuthorized,);
 			ensure!(*who == principal.unwrap(), Error::<T>::NotAuthorized,);
+			ensure!(Self::is_target_allowed(&target), Error::<T>::TargetNotAllowed,);
 			ensure!(
 				<MessageQueue<T>>::decode_len().unwrap_or(0)
 					< T::MaxMessagesPerCommit::get() as usize,
 				Error::<T>::QueueSizeLimitReached,
 			);
-			ensure!(
-				payload.len() <= T::MaxMessagePayloadSize::get() as usize,
-				Error::<T>::PayloadTooLarge,
-			);
+			let payload: BoundedVec<u8, T::MaxMessagePayloadSize> =
+				payload.to_vec().try_into().map_err(|_| Error::<T>::PayloadTooLarge)?;
 
 			let next_id = <NextId<T>>::get();
 			if next_id.checked_add(1).is_none() {
 				return Err(Error::<T>::Overflow.into());
 			}
 
-			<MessageQueue<T>>::try_append(Message {
-				id: next_id,
-				target,
-				payload: payload.to_vec(),
-			})
+			if T::DeduplicateMessages::get() {
+				let message_hash = <T as Config>::Hashing::hash(&(target, &payload).encode());
+				ensure!(
+					!<PendingMessageHashes<T>>::get().contains(&message_hash),
+					Error::<T>::DuplicateMessage,
+				);
+				<PendingMessageHashes<T>>::try_append(message_hash)
+					.map_err(|_| Error::<T>::QueueSizeLimitReached)?;
+			}
+
+			<MessageQueue<T>>::try_append(Message { id: next_id, target, payload })
 			.map_err(|_| Error::<T>::QueueSizeLimitReached)?;
 			Self::deposit_event(Event::MessageAccepted(next_id));
 
@@ -202,33 +380,93 @@ pub mod pallet {
 			Ok(())
 		}
 
-		fn commit() -> Weight {
-			let messages: BoundedVec<Message, T::MaxMessagesPerCommit> = <MessageQueue<T>>::take();
-			if messages.is_empty() {
+		fn is_target_allowed(target: &H160) -> bool {
+			<AllowedTargets<T>>::iter_keys().next().is_none() ||
+				<AllowedTargets<T>>::contains_key(target)
+		}
+
+		fn queue_reached_threshold() -> bool {
+			let threshold = T::CommitThreshold::get().mul_ceil(T::MaxMessagesPerCommit::get());
+			if threshold.is_zero() {
+				return false;
+			}
+
+			<MessageQueue<T>>::decode_len().unwrap_or(0) >= threshold as usize
+		}
+
+		fn is_heartbeat(now: T::BlockNumber) -> bool {
+			let heartbeat_interval = T::HeartbeatInterval::get();
+			!heartbeat_interval.is_zero() && (now % heartbeat_interval).is_zero()
+		}
+
+		fn commit(now: T::BlockNumber) -> Weight {
+			let messages: BoundedVec<Message<T::MaxMessagePayloadSize>, T::MaxMessagesPerCommit> =
+				<MessageQueue<T>>::take();
+			<PendingMessageHashes<T>>::kill();
+			if messages.is_empty() && !Self::is_heartbeat(now) {
 				return T::WeightInfo::on_initialize_no_messages();
 			}
 
 			let nonce = <Nonce<T>>::get();
 			let next_nonce = nonce.saturating_add(1);
 			<Nonce<T>>::put(next_nonce);
-
-			let bundle =
-				MessageBundle { nonce: next_nonce, messages: messages.clone().into_inner() };
-
-			let commitment_hash = Self::make_commitment_hash(&bundle);
-			let average_payload_size = Self::average_payload_size(&bundle.messages);
+			<LastCommitBlock<T>>::put(now);
+
+			let bundle = MessageBundle {
+				version: COMMITMENT_VERSION,
+				nonce: next_nonce,
+				// Fix each message's position, so the Ethereum side can enforce the order
+				// regardless of how a relayer arranges them. The index is only added here, so
+				// that the encoding of the stored `MessageQueue` is unchanged.
+				messages: messages
+					.iter()
+					.cloned()
+					.enumerate()
+					.map(|(index, message)| CommittedMessage {
+						id: message.id,
+						target: message.target,
+						payload: message.payload,
+						commitment_index: index as u32,
+					})
+					.collect(),
+			};
+
+			let commitment_hash = Self::compute_commitment_hash(&bundle);
+			let encoded_bundle = bundle.encode();
+
+			// A history size of zero keeps no entries at all.
+			let history_size = T::CommitmentHistorySize::get();
+			if history_size > 0 {
+				<NonceToCommitmentHash<T>>::insert(next_nonce, commitment_hash);
+				<NonceToBundleChecksum<T>>::insert(
+					next_nonce,
+					<T as Config>::Hashing::hash(&encoded_bundle),
+				);
+				if let Some(expired_nonce) = next_nonce.checked_sub(history_size) {
+					<NonceToCommitmentHash<T>>::remove(expired_nonce);
+					<NonceToBundleChecksum<T>>::remove(expired_nonce);
+				}
+			}
+			let average_payload_size = Self::average_payload_size(&messages);
 
 			let digest_item =
 				AuxiliaryDigestItem::Commitment(ChannelId::Basic, commitment_hash.clone()).into();
 			<frame_system::Pallet<T>>::deposit_log(digest_item);
 
-			let key = Self::make_offchain_key(commitment_hash);
-			offchain_index::set(&*key, &bundle.encode());
+			let key = Self::offchain_key(commitment_hash);
+			offchain_index::set(&*key, &encoded_bundle);
+
+			Self::deposit_event(Event::Committed {
+				nonce: next_nonce,
+				message_count: messages.len() as u32,
+				average_payload_size: average_payload_size as u32,
+			});
 
 			T::WeightInfo::on_initialize(messages.len() as u32, average_payload_size as u32)
 		}
 
-		fn make_commitment_hash(bundle: &MessageBundle) -> H256 {
+		/// Commitment hash of `bundle`, as included in the block digest when it is committed.
+		pub fn compute_commitment_hash(bundle: &MessageBundle<T::MaxMessagePayloadSize>) -> H256 {
 			let messages: Vec<Token> = bundle
 				.messages
 				.iter()
@@ -236,7 +474,8 @@ pub mod pallet {
 					Token::Tuple(vec![
 						Token::Uint(message.id.into()),
 						Token::Address(message.target),
-						Token::Bytes(message.payload.clone()),
+						Token::Bytes(message.payload.to_vec()),
+						Token::Uint(message.commitment_index.into()),
 					])
 				})
 				.collect();
@@ -247,15 +486,58 @@ pub mod pallet {
 			<T as Config>::Hashing::hash(&input)
 		}
 
-		fn average_payload_size(messages: &[Message]) -> usize {
+		fn average_payload_size(messages: &[Message<T::MaxMessagePayloadSize>]) -> usize {
 			let sum: usize = messages.iter().fold(0, |acc, x| acc + x.payload.len());
 			// We overestimate message payload size rather than underestimate.
 			// So add 1 here to account for integer division truncation.
-			(sum / messages.len()).saturating_add(1)
+			// Heartbeat commitments have no messages, hence the checked division.
+			sum.checked_div(messages.len()).unwrap_or(0).saturating_add(1)
+		}
+
+		/// Commitment hash of the message bundle committed with `nonce`, for relayers which
+		/// missed the corresponding block digest. Only the last
+		/// [`Config::CommitmentHistorySize`] nonces are kept.
+		pub fn commitment_hash_by_nonce(nonce: u64) -> Option<H256> {
+			<NonceToCommitmentHash<T>>::get(nonce)
+		}
+
+		/// Whether a message bundle with `nonce` has been committed by this channel.
+		pub fn is_nonce_committed(nonce: u64) -> bool {
+			nonce > 0 && nonce <= <Nonce<T>>::get()
+		}
+
+		/// Commitment hash of a SCALE-encoded message bundle, as fetched from offchain storage,
+		/// or `None` if it doesn't decode. Lets relayers check a fetched bundle against the
+		/// digest without re-implementing the ABI encoding.
+		pub fn commitment_hash_of_encoded_bundle(encoded: Vec<u8>) -> Option<H256> {
+			let bundle =
+				MessageBundle::<T::MaxMessagePayloadSize>::decode(&mut encoded.as_slice()).ok()?;
+			Some(Self::compute_commitment_hash(&bundle))
+		}
+
+		/// Whether `encoded` is exactly the message bundle written to offchain storage for
+		/// `nonce`.
+		///
+		/// A relayer whose fetched bundle fails this check has corrupted offchain data, rather
+		/// than missing data. It can recover by rebuilding the bundle from `MessageQueue` as of
+		/// the parent of the block that committed `nonce`. Messages are numbered by queue
+		/// position as their `commitment_index`. The rebuilt bundle can be checked against
+		/// [`Pallet::commitment_hash_by_nonce`].
+		pub fn is_offchain_bundle_intact(nonce: u64, encoded: Vec<u8>) -> bool {
+			<NonceToBundleChecksum<T>>::get(nonce) ==
+				Some(<T as Config>::Hashing::hash(&encoded))
+		}
+
+		/// Offchain storage key for the message bundle committed under `hash`.
+		///
+		/// Relayers should use this (via the runtime API) rather than re-implementing
+		/// the key derivation.
+		pub fn offchain_key(hash: H256) -> Vec<u8> {
+			Self::make_offchain_key(COMMITMENT_VERSION, hash)
 		}
 
-		fn make_offchain_key(hash: H256) -> Vec<u8> {
-			(T::INDEXING_PREFIX, ChannelId::Basic, hash).encode()
+		pub(super) fn make_offchain_key(version: u8, hash: H256) -> Vec<u8> {
+			(version, T::INDEXING_PREFIX, ChannelId::Basic, hash).encode()
 		}
 	}
 }
@@ -11,7 +11,7 @@ use sp_keyring::AccountKeyring as Keyring;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Keccak256, Verify},
-	MultiSignature,
+	MultiSignature, Perbill,
 };
 use sp_std::convert::From;
 
@@ -66,8 +66,12 @@ impl frame_system::Config for Test {
 }
 
 parameter_types! {
-	pub const MaxMessagePayloadSize: u64 = 128;
+	pub const MaxMessagePayloadSize: u32 = 128;
 	pub const MaxMessagesPerCommit: u32 = 5;
+	pub const CommitThreshold: Perbill = Perbill::from_percent(60);
+	pub storage HeartbeatInterval: u64 = 0;
+	pub storage DeduplicateMessages: bool = false;
+	pub storage CommitmentHistorySize: u64 = 2;
 }
 
 impl basic_outbound_channel::Config for Test {
@@ -76,6 +80,10 @@ impl basic_outbound_channel::Config for Test {
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type CommitThreshold = CommitThreshold;
+	type HeartbeatInterval = HeartbeatInterval;
+	type DeduplicateMessages = DeduplicateMessages;
+	type CommitmentHistorySize = CommitmentHistorySize;
 	type SetPrincipalOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type WeightInfo = ();
 }
@@ -118,6 +126,359 @@ fn test_submit() {
 	});
 }
 
+#[test]
+fn test_commit_event_carries_payload_figures() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0; 10]));
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0; 21]));
+
+		run_to_block(2);
+
+		// (10 + 21) / 2, rounded up by one
+		System::assert_last_event(
+			Event::BasicOutboundChannel(crate::outbound::Event::Committed {
+				nonce: 1,
+				message_count: 2,
+				average_payload_size: 16,
+			}),
+		);
+	});
+}
+
+#[test]
+fn test_compute_commitment_hash_matches_commit() {
+	new_tester().execute_with(|| {
+		let target = H160::repeat_byte(2);
+		let who: AccountId = Keyring::Bob.into();
+
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+		run_to_block(2);
+
+		let bundle = MessageBundle::<MaxMessagePayloadSize> {
+			version: COMMITMENT_VERSION,
+			nonce: 1,
+			messages: vec![CommittedMessage {
+				id: 0,
+				target,
+				payload: vec![0, 1, 2].try_into().unwrap(),
+				commitment_index: 0,
+			}],
+		};
+		let committed_hash = BasicOutboundChannel::commitment_hash_by_nonce(1).unwrap();
+
+		assert_eq!(BasicOutboundChannel::compute_commitment_hash(&bundle), committed_hash);
+		assert_eq!(
+			BasicOutboundChannel::commitment_hash_of_encoded_bundle(bundle.encode()),
+			Some(committed_hash)
+		);
+		assert_eq!(BasicOutboundChannel::commitment_hash_of_encoded_bundle(vec![0xff]), None);
+	});
+}
+
+#[test]
+fn test_offchain_bundle_checksum_detects_tampering() {
+	new_tester().execute_with(|| {
+		let target = H160::repeat_byte(2);
+		let who: AccountId = Keyring::Bob.into();
+
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+		run_to_block(2);
+
+		let bundle = MessageBundle::<MaxMessagePayloadSize> {
+			version: COMMITMENT_VERSION,
+			nonce: 1,
+			messages: vec![CommittedMessage {
+				id: 0,
+				target,
+				payload: vec![0, 1, 2].try_into().unwrap(),
+				commitment_index: 0,
+			}],
+		};
+		let encoded = bundle.encode();
+		assert!(BasicOutboundChannel::is_offchain_bundle_intact(1, encoded.clone()));
+
+		let mut tampered = encoded.clone();
+		*tampered.last_mut().unwrap() ^= 1;
+		assert!(!BasicOutboundChannel::is_offchain_bundle_intact(1, tampered));
+
+		assert!(!BasicOutboundChannel::is_offchain_bundle_intact(2, encoded));
+	});
+}
+
+#[test]
+fn test_commit_assigns_commitment_indices_in_queue_order() {
+	new_tester().execute_with(|| {
+		let target = H160::repeat_byte(2);
+		let who: AccountId = Keyring::Bob.into();
+
+		let payloads: Vec<Vec<u8>> = vec![vec![3], vec![1], vec![2]];
+		for payload in payloads.iter() {
+			assert_ok!(BasicOutboundChannel::submit(&who, target, payload));
+		}
+		run_to_block(2);
+
+		let bundle = MessageBundle::<MaxMessagePayloadSize> {
+			version: COMMITMENT_VERSION,
+			nonce: 1,
+			messages: payloads
+				.into_iter()
+				.enumerate()
+				.map(|(index, payload)| CommittedMessage {
+					id: index as u64,
+					target,
+					payload: payload.try_into().unwrap(),
+					commitment_index: index as u32,
+				})
+				.collect(),
+		};
+		assert_eq!(
+			BasicOutboundChannel::commitment_hash_by_nonce(1),
+			Some(BasicOutboundChannel::compute_commitment_hash(&bundle)),
+		);
+	});
+}
+
+#[test]
+fn test_commit_on_interval() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		<Interval<Test>>::put(10);
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+
+		run_to_block(9);
+		assert_eq!(<Nonce<Test>>::get(), 0);
+
+		run_to_block(10);
+		assert_eq!(<Nonce<Test>>::get(), 1);
+	});
+}
+
+#[test]
+fn test_commit_early_when_queue_reaches_threshold() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		<Interval<Test>>::put(10);
+
+		// Threshold is 60% of 5 messages, i.e. 3 messages.
+		(0..2).for_each(|_| BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]).unwrap());
+		run_to_block(2);
+		assert_eq!(<Nonce<Test>>::get(), 0);
+
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+		run_to_block(3);
+		assert_eq!(<Nonce<Test>>::get(), 1);
+		assert!(<MessageQueue<Test>>::get().is_empty());
+	});
+}
+
+#[test]
+fn test_heartbeat_commits_empty_queue() {
+	new_tester().execute_with(|| {
+		HeartbeatInterval::set(&3);
+
+		run_to_block(2);
+		assert_eq!(<Nonce<Test>>::get(), 0);
+
+		run_to_block(3);
+		assert_eq!(<Nonce<Test>>::get(), 1);
+		assert_eq!(System::digest().logs().len(), 1);
+
+		run_to_block(5);
+		assert_eq!(<Nonce<Test>>::get(), 1);
+
+		run_to_block(6);
+		assert_eq!(<Nonce<Test>>::get(), 2);
+	});
+}
+
+#[test]
+fn test_no_heartbeat_when_disabled() {
+	new_tester().execute_with(|| {
+		run_to_block(6);
+		assert_eq!(<Nonce<Test>>::get(), 0);
+		assert!(System::digest().logs().is_empty());
+	});
+}
+
+#[test]
+fn test_duplicate_message_rejected_within_commit_cycle() {
+	new_tester().execute_with(|| {
+		DeduplicateMessages::set(&true);
+
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		<Interval<Test>>::put(10);
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+		assert_noop!(
+			BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]),
+			Error::<Test>::DuplicateMessage,
+		);
+
+		// Same payload to a different target, or a different payload, is accepted.
+		assert_ok!(BasicOutboundChannel::submit(&who, H160::repeat_byte(1), &vec![0, 1, 2]));
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 3]));
+
+		run_to_block(10);
+		assert_eq!(<Nonce<Test>>::get(), 1);
+
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+	});
+}
+
+#[test]
+fn test_duplicate_message_accepted_when_deduplication_disabled() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+	});
+}
+
+#[test]
+fn test_force_commit() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		<Interval<Test>>::put(10);
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+
+		assert_ok!(BasicOutboundChannel::force_commit(Origin::signed(who)));
+		assert_eq!(<Nonce<Test>>::get(), 1);
+		assert!(<MessageQueue<Test>>::get().is_empty());
+		assert_eq!(System::digest().logs().len(), 1);
+	});
+}
+
+#[test]
+fn test_force_commit_rejected_after_commit_in_same_block() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+		run_to_block(2);
+		assert_eq!(<Nonce<Test>>::get(), 1);
+
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![3, 4, 5]));
+		assert_noop!(
+			BasicOutboundChannel::force_commit(Origin::signed(who)),
+			Error::<Test>::AlreadyCommitted,
+		);
+
+		run_to_block(3);
+		assert_eq!(<Nonce<Test>>::get(), 2);
+		assert!(<MessageQueue<Test>>::get().is_empty());
+	});
+}
+
+#[test]
+fn test_force_commit_not_authorized() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let bob: AccountId = Keyring::Bob.into();
+		let charlie: AccountId = Keyring::Charlie.into();
+
+		assert_ok!(BasicOutboundChannel::submit(&bob, target, &vec![0, 1, 2]));
+
+		assert_noop!(
+			BasicOutboundChannel::force_commit(Origin::signed(charlie)),
+			Error::<Test>::NotAuthorized,
+		);
+	});
+}
+
+#[test]
+fn test_commitment_hash_recorded_by_nonce() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		assert_eq!(BasicOutboundChannel::commitment_hash_by_nonce(1), None);
+
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+		run_to_block(2);
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![3, 4, 5]));
+		run_to_block(3);
+
+		assert_eq!(<Nonce<Test>>::get(), 2);
+		let first = BasicOutboundChannel::commitment_hash_by_nonce(1).unwrap();
+		let second = BasicOutboundChannel::commitment_hash_by_nonce(2).unwrap();
+		assert_ne!(first, second);
+
+		let logs = System::digest().logs().to_vec();
+		assert_eq!(logs.len(), 2);
+		assert_eq!(logs[0], AuxiliaryDigestItem::Commitment(ChannelId::Basic, first).into());
+		assert_eq!(logs[1], AuxiliaryDigestItem::Commitment(ChannelId::Basic, second).into());
+		assert_eq!(BasicOutboundChannel::commitment_hash_by_nonce(3), None);
+	});
+}
+
+#[test]
+fn test_commitment_hashes_pruned_beyond_history_size() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		for (block, payload) in [(2, 0u8), (3, 1), (4, 2)] {
+			assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![payload]));
+			run_to_block(block);
+		}
+
+		assert_eq!(<Nonce<Test>>::get(), 3);
+		assert_eq!(BasicOutboundChannel::commitment_hash_by_nonce(1), None);
+		assert!(BasicOutboundChannel::commitment_hash_by_nonce(2).is_some());
+		assert!(BasicOutboundChannel::commitment_hash_by_nonce(3).is_some());
+		assert!(BasicOutboundChannel::is_nonce_committed(1));
+		assert!(!<NonceToBundleChecksum<Test>>::contains_key(1));
+		assert!(<NonceToBundleChecksum<Test>>::contains_key(3));
+	});
+}
+
+#[test]
+fn test_zero_commitment_history_size_keeps_no_entries() {
+	new_tester().execute_with(|| {
+		CommitmentHistorySize::set(&0);
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		for (block, payload) in [(2, 0u8), (3, 1)] {
+			assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![payload]));
+			run_to_block(block);
+		}
+
+		assert_eq!(<Nonce<Test>>::get(), 2);
+		assert_eq!(<NonceToCommitmentHash<Test>>::iter().count(), 0);
+		assert_eq!(<NonceToBundleChecksum<Test>>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn test_is_nonce_committed() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+		assert!(!BasicOutboundChannel::is_nonce_committed(1));
+
+		run_to_block(2);
+		assert!(BasicOutboundChannel::is_nonce_committed(1));
+		assert!(!BasicOutboundChannel::is_nonce_committed(2));
+		assert!(!BasicOutboundChannel::is_nonce_committed(0));
+	});
+}
+
 #[test]
 fn test_submit_exceeds_queue_limit() {
 	new_tester().execute_with(|| {
@@ -151,6 +512,24 @@ fn test_submit_exceeds_payload_limit() {
 	})
 }
 
+#[test]
+fn test_submit_payload_at_limit() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		let payload = vec![1u8; MaxMessagePayloadSize::get() as usize];
+		assert_ok!(BasicOutboundChannel::submit(&who, target, payload.as_slice()));
+		assert_eq!(<MessageQueue<Test>>::get()[0].payload.len(), payload.len());
+	})
+}
+
+#[test]
+fn test_bounded_payload_rejects_oversized() {
+	let payload = vec![1u8; MaxMessagePayloadSize::get() as usize + 1];
+	assert!(BoundedVec::<u8, MaxMessagePayloadSize>::try_from(payload).is_err());
+}
+
 #[test]
 fn test_submit_fails_not_authorized() {
 	new_tester().execute_with(|| {
@@ -182,6 +561,165 @@ fn test_set_principal() {
 		let alice: AccountId = Keyring::Alice.into();
 
 		assert_ok!(BasicOutboundChannel::set_principal(Origin::root(), alice.clone()));
-		assert_eq!(<Principal<Test>>::get(), Some(alice));
+		assert_eq!(<Principal<Test>>::get(), Some(alice.clone()));
+		System::assert_last_event(
+			Event::BasicOutboundChannel(crate::outbound::Event::PrincipalChanged {
+				old: Some(Keyring::Bob.into()),
+				new: alice,
+			}),
+		);
+	});
+}
+
+#[test]
+fn test_principal_handover() {
+	new_tester().execute_with(|| {
+		let alice: AccountId = Keyring::Alice.into();
+		let charlie: AccountId = Keyring::Charlie.into();
+
+		assert_ok!(BasicOutboundChannel::propose_principal(Origin::root(), alice.clone()));
+		System::assert_last_event(
+			Event::BasicOutboundChannel(crate::outbound::Event::PrincipalProposed {
+				proposed: alice.clone(),
+			}),
+		);
+
+		assert_noop!(
+			BasicOutboundChannel::accept_principal(Origin::signed(charlie)),
+			Error::<Test>::NotProposedPrincipal,
+		);
+
+		assert_ok!(BasicOutboundChannel::accept_principal(Origin::signed(alice.clone())));
+		assert_eq!(<Principal<Test>>::get(), Some(alice.clone()));
+		assert_eq!(BasicOutboundChannel::pending_principal(), None);
+		System::assert_last_event(
+			Event::BasicOutboundChannel(crate::outbound::Event::PrincipalAccepted {
+				old: Some(Keyring::Bob.into()),
+				new: alice.clone(),
+			}),
+		);
+
+		assert_ok!(BasicOutboundChannel::submit(&alice, H160::zero(), &vec![0, 1, 2]));
+	});
+}
+
+#[test]
+fn test_proposed_principal_does_not_replace_current_until_accepted() {
+	new_tester().execute_with(|| {
+		let alice: AccountId = Keyring::Alice.into();
+		let bob: AccountId = Keyring::Bob.into();
+
+		assert_noop!(
+			BasicOutboundChannel::propose_principal(Origin::signed(bob.clone()), alice.clone()),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(BasicOutboundChannel::propose_principal(Origin::root(), alice.clone()));
+		assert_eq!(BasicOutboundChannel::pending_principal(), Some(alice.clone()));
+		assert_eq!(<Principal<Test>>::get(), Some(bob.clone()));
+
+		assert_ok!(BasicOutboundChannel::submit(&bob, H160::zero(), &vec![0, 1, 2]));
+		assert_noop!(
+			BasicOutboundChannel::submit(&alice, H160::zero(), &vec![0, 1, 2]),
+			Error::<Test>::NotAuthorized,
+		);
+	});
+}
+
+#[test]
+fn test_set_principal_discards_pending_handover() {
+	new_tester().execute_with(|| {
+		let alice: AccountId = Keyring::Alice.into();
+		let charlie: AccountId = Keyring::Charlie.into();
+
+		assert_ok!(BasicOutboundChannel::propose_principal(Origin::root(), alice.clone()));
+		assert_ok!(BasicOutboundChannel::set_principal(Origin::root(), charlie.clone()));
+		assert_eq!(BasicOutboundChannel::pending_principal(), None);
+
+		assert_noop!(
+			BasicOutboundChannel::accept_principal(Origin::signed(alice)),
+			Error::<Test>::NotProposedPrincipal,
+		);
+		assert_eq!(<Principal<Test>>::get(), Some(charlie));
+	});
+}
+
+#[test]
+fn test_offchain_key_includes_version() {
+	new_tester().execute_with(|| {
+		let hash = H256::repeat_byte(1);
+
+		let key = BasicOutboundChannel::make_offchain_key(COMMITMENT_VERSION, hash);
+		assert_eq!(key[0], COMMITMENT_VERSION);
+		assert_eq!(
+			key,
+			(COMMITMENT_VERSION, <Test as Config>::INDEXING_PREFIX, ChannelId::Basic, hash).encode()
+		);
+
+		assert_ne!(
+			BasicOutboundChannel::make_offchain_key(0, hash),
+			BasicOutboundChannel::make_offchain_key(1, hash),
+		);
+	});
+}
+
+#[test]
+fn test_offchain_key_matches_commit_key() {
+	new_tester().execute_with(|| {
+		let hash = H256::repeat_byte(2);
+
+		assert_eq!(
+			BasicOutboundChannel::offchain_key(hash),
+			BasicOutboundChannel::make_offchain_key(COMMITMENT_VERSION, hash),
+		);
+	});
+}
+
+#[test]
+fn test_submit_to_allowed_target() {
+	new_tester().execute_with(|| {
+		let target = H160::repeat_byte(1);
+		let who: AccountId = Keyring::Bob.into();
+
+		assert_ok!(BasicOutboundChannel::add_target(Origin::root(), target));
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+	});
+}
+
+#[test]
+fn test_submit_to_disallowed_target() {
+	new_tester().execute_with(|| {
+		let who: AccountId = Keyring::Bob.into();
+
+		assert_ok!(BasicOutboundChannel::add_target(Origin::root(), H160::repeat_byte(1)));
+		assert_noop!(
+			BasicOutboundChannel::submit(&who, H160::repeat_byte(2), &vec![0, 1, 2]),
+			Error::<Test>::TargetNotAllowed,
+		);
+
+		assert_ok!(BasicOutboundChannel::remove_target(Origin::root(), H160::repeat_byte(1)));
+		assert_ok!(BasicOutboundChannel::submit(&who, H160::repeat_byte(2), &vec![0, 1, 2]));
+	});
+}
+
+#[test]
+fn test_submit_with_no_allowed_targets() {
+	new_tester().execute_with(|| {
+		let who: AccountId = Keyring::Bob.into();
+
+		assert_ok!(BasicOutboundChannel::submit(&who, H160::repeat_byte(1), &vec![0, 1, 2]));
+		assert_ok!(BasicOutboundChannel::submit(&who, H160::repeat_byte(2), &vec![0, 1, 2]));
+	});
+}
+
+#[test]
+fn test_add_target_unauthorized() {
+	new_tester().execute_with(|| {
+		let dave: AccountId = Keyring::Dave.into();
+
+		assert_noop!(
+			BasicOutboundChannel::add_target(Origin::signed(dave), H160::repeat_byte(1)),
+			DispatchError::BadOrigin
+		);
 	});
 }
@@ -41,6 +41,8 @@ pub trait WeightInfo {
 	fn on_initialize_non_interval() -> Weight;
 	fn on_initialize_no_messages() -> Weight;
 	fn set_principal() -> Weight;
+	fn add_target() -> Weight;
+	fn remove_target() -> Weight;
 }
 
 /// Weights for basic_channel::outbound using the Snowbridge node and recommended hardware.
@@ -67,6 +69,14 @@ impl<T: frame_system::Config> WeightInfo for SnowbridgeWeight<T> {
 		(2_544_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn add_target() -> Weight {
+		(2_544_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_target() -> Weight {
+		(2_544_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -92,4 +102,12 @@ impl WeightInfo for () {
 		(2_544_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn add_target() -> Weight {
+		(2_544_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn remove_target() -> Weight {
+		(2_544_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
@@ -15,7 +15,7 @@ use sp_core::{H160, H256};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Keccak256, Verify},
-	MultiSignature,
+	MultiSignature, Perbill,
 };
 
 use snowbridge_core::ChannelId;
@@ -125,8 +125,12 @@ impl pallet_assets::Config for Test {
 parameter_types! {
 	pub const EtherAssetId: u128 = 0;
 	pub const EtherAppPalletId: PalletId = PalletId(*b"etherapp");
-	pub const MaxMessagePayloadSize: u64 = 256;
+	pub const MaxMessagePayloadSize: u32 = 256;
 	pub const MaxMessagesPerCommit: u32 = 3;
+	pub const CommitThreshold: Perbill = Perbill::from_percent(0);
+	pub const HeartbeatInterval: u64 = 0;
+	pub const DeduplicateMessages: bool = false;
+	pub const CommitmentHistorySize: u64 = 16;
 }
 
 pub type Ether = ItemOf<Assets, EtherAssetId, AccountId>;
@@ -137,6 +141,10 @@ impl snowbridge_basic_channel::outbound::Config for Test {
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type CommitThreshold = CommitThreshold;
+	type HeartbeatInterval = HeartbeatInterval;
+	type DeduplicateMessages = DeduplicateMessages;
+	type CommitmentHistorySize = CommitmentHistorySize;
 	type SetPrincipalOrigin = frame_system::EnsureRoot<AccountId>;
 	type WeightInfo = ();
 }
@@ -14,7 +14,7 @@ use sp_runtime::{
 	traits::{
 		AccountIdConversion, BlakeTwo256, IdentifyAccount, IdentityLookup, Keccak256, Verify,
 	},
-	DispatchError, MultiSignature,
+	DispatchError, MultiSignature, Perbill,
 };
 
 use snowbridge_core::{
@@ -157,8 +157,12 @@ parameter_types! {
 	pub const EtherAssetId: u128 = 0;
 	pub const EtherAppPalletId: PalletId = PalletId(*b"etherapp");
 	pub const Erc20AppPalletId: PalletId = PalletId(*b"erc20app");
-	pub const MaxMessagePayloadSize: u64 = 256;
+	pub const MaxMessagePayloadSize: u32 = 256;
 	pub const MaxMessagesPerCommit: u32 = 3;
+	pub const CommitThreshold: Perbill = Perbill::from_percent(0);
+	pub const HeartbeatInterval: u64 = 0;
+	pub const DeduplicateMessages: bool = false;
+	pub const CommitmentHistorySize: u64 = 16;
 }
 
 pub type Ether = ItemOf<Assets, EtherAssetId, AccountId>;
@@ -169,6 +173,10 @@ impl snowbridge_basic_channel::outbound::Config for Test {
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type CommitThreshold = CommitThreshold;
+	type HeartbeatInterval = HeartbeatInterval;
+	type DeduplicateMessages = DeduplicateMessages;
+	type CommitmentHistorySize = CommitmentHistorySize;
 	type SetPrincipalOrigin = frame_system::EnsureRoot<AccountId>;
 	type WeightInfo = ();
 }
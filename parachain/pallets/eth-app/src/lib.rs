@@ -15,6 +15,7 @@
 //! - `burn`: Burn an ETH balance.
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod migration;
 mod payload;
 pub mod weights;
 
@@ -33,8 +34,10 @@ use frame_support::{
 	traits::{fungible::Mutate, EnsureOrigin},
 	transactional, PalletId,
 };
+use codec::{Decode, Encode};
 use frame_system::ensure_signed;
-use sp_core::H160;
+use scale_info::TypeInfo;
+use sp_core::{RuntimeDebug, H160};
 use sp_runtime::traits::StaticLookup;
 use sp_std::prelude::*;
 
@@ -43,6 +46,29 @@ use snowbridge_core::{
 	ChannelId, OutboundRouter,
 };
 
+/// Scaling between amounts of wei and balances of the local ETH asset, for when the asset is
+/// configured with fewer decimals than ETH: `balance = wei * multiplier / divisor`.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct DecimalsConversion {
+	pub multiplier: u128,
+	pub divisor: u128,
+}
+
+impl DecimalsConversion {
+	/// The local asset has the same 18 decimals as ETH.
+	pub const IDENTITY: Self = Self { multiplier: 1, divisor: 1 };
+
+	/// Local balance for an amount of wei, rounded down.
+	pub fn to_balance(&self, wei: u128) -> Option<u128> {
+		wei.checked_mul(self.multiplier)?.checked_div(self.divisor)
+	}
+
+	/// Amount of wei for a local balance, rounded down.
+	pub fn to_wei(&self, balance: u128) -> Option<u128> {
+		balance.checked_mul(self.divisor)?.checked_div(self.multiplier)
+	}
+}
+
 pub use pallet::*;
 use payload::OutboundPayload;
 pub use weights::WeightInfo;
@@ -57,6 +83,7 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(migration::STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -74,10 +101,28 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 
 		type XcmReserveTransfer: XcmReserveTransfer<Self::AccountId, Self::Origin>;
+
+		/// Conversion between wei and balances of [`Config::Asset`].
+		#[pallet::constant]
+		type DecimalsConversion: Get<DecimalsConversion>;
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migration::v1::migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migration::v1::pre_migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migration::v1::post_migrate::<T>()
+		}
+	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -90,8 +135,24 @@ pub mod pallet {
 	#[pallet::getter(fn address)]
 	pub(super) type Address<T: Config> = StorageValue<_, H160, ValueQuery>;
 
+	/// Total ETH minted on this chain and not yet burned, in local balance units, seeded from
+	/// the asset's total issuance when it was introduced. See
+	/// [`Pallet::minted_supply_in_wei`] for reconciling against the amount locked on Ethereum.
+	#[pallet::storage]
+	#[pallet::getter(fn minted_supply)]
+	pub(super) type MintedSupply<T: Config> = StorageValue<_, u128, ValueQuery>;
+
+	/// Wei locked on Ethereum by incoming transfers but too small to mint as a local balance.
+	/// Together with [`MintedSupply`] it accounts for everything locked on Ethereum.
+	#[pallet::storage]
+	#[pallet::getter(fn unminted_dust)]
+	pub(super) type UnmintedDust<T: Config> = StorageValue<_, u128, ValueQuery>;
+
 	#[pallet::error]
-	pub enum Error<T> {}
+	pub enum Error<T> {
+		/// The amount can't be converted between wei and the local balance.
+		ConversionOverflow,
+	}
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
@@ -130,10 +191,17 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
+			// Only burn the part of the balance that is a whole amount of wei, leaving any
+			// remainder with the holder.
+			let conversion = T::DecimalsConversion::get();
+			let wei = conversion.to_wei(amount).ok_or(Error::<T>::ConversionOverflow)?;
+			let amount = conversion.to_balance(wei).ok_or(Error::<T>::ConversionOverflow)?;
+
 			T::Asset::burn_from(&who, amount)?;
+			<MintedSupply<T>>::mutate(|supply| *supply = supply.saturating_sub(amount));
 
 			let message =
-				OutboundPayload { sender: who.clone(), recipient: recipient.clone(), amount };
+				OutboundPayload { sender: who.clone(), recipient: recipient.clone(), amount: wei };
 
 			T::OutboundRouter::submit(channel_id, &who, <Address<T>>::get(), &message.encode())?;
 			Self::deposit_event(Event::Burned(who.clone(), recipient, amount));
@@ -156,7 +224,19 @@ pub mod pallet {
 			}
 
 			let recipient = T::Lookup::lookup(recipient)?;
+			// The wei are already locked on Ethereum, so mint what converts and record the
+			// remainder rather than rejecting the transfer.
+			let conversion = T::DecimalsConversion::get();
+			let wei = amount;
+			let amount = conversion.to_balance(wei).ok_or(Error::<T>::ConversionOverflow)?;
+			let dust = wei.saturating_sub(
+				conversion.to_wei(amount).ok_or(Error::<T>::ConversionOverflow)?,
+			);
 			T::Asset::mint_into(&recipient, amount)?;
+			<MintedSupply<T>>::mutate(|supply| *supply = supply.saturating_add(amount));
+			if dust > 0 {
+				<UnmintedDust<T>>::mutate(|total| *total = total.saturating_add(dust));
+			}
 			Self::deposit_event(Event::Minted(sender, recipient.clone(), amount));
 
 			if let Some(destination) = destination {
@@ -177,4 +257,11 @@ pub mod pallet {
 			Ok(())
 		}
 	}
+
+	impl<T: Config> Pallet<T> {
+		/// [`MintedSupply`] in wei, for reconciling against the amount locked on Ethereum.
+		pub fn minted_supply_in_wei() -> Option<u128> {
+			T::DecimalsConversion::get().to_wei(<MintedSupply<T>>::get())
+		}
+	}
 }
@@ -0,0 +1,58 @@
+//! Storage migrations for the ETH app.
+//!
+//! Each migration checks the on-chain storage version before running, so it is safe to leave
+//! in `on_runtime_upgrade` after it has been applied.
+
+use super::*;
+use frame_support::{
+	traits::{fungible::Inspect, Get, GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+
+/// Storage version expected by the current code.
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+/// Version 1 adds `MintedSupply`, which is seeded from the total issuance of the ETH asset,
+/// all of which was minted by the bridge.
+pub mod v1 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let supply = T::Asset::total_issuance();
+		<MintedSupply<T>>::put(supply);
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		log::info!(target: "eth-app", "Migrated storage to v1, minted supply {}.", supply);
+
+		T::DbWeight::get().reads_writes(2, 2)
+	}
+
+	#[cfg(any(test, feature = "try-runtime"))]
+	pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return Ok(())
+		}
+		if <MintedSupply<T>>::exists() {
+			return Err("MintedSupply is already set before migrating to v1")
+		}
+
+		Ok(())
+	}
+
+	#[cfg(any(test, feature = "try-runtime"))]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() != 1 {
+			return Err("Storage version is not 1 after migrating")
+		}
+		if <MintedSupply<T>>::get() != T::Asset::total_issuance() {
+			return Err("MintedSupply does not match the total issuance of the asset")
+		}
+
+		Ok(())
+	}
+}
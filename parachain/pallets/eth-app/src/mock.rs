@@ -13,7 +13,7 @@ use sp_core::{H160, H256};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Keccak256, Verify},
-	MultiSignature,
+	MultiSignature, Perbill,
 };
 
 use snowbridge_core::{
@@ -21,6 +21,8 @@ use snowbridge_core::{
 	ChannelId,
 };
 
+use crate::DecimalsConversion;
+
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -148,8 +150,12 @@ where
 }
 
 parameter_types! {
-	pub const MaxMessagePayloadSize: u64 = 256;
+	pub const MaxMessagePayloadSize: u32 = 256;
 	pub const MaxMessagesPerCommit: u32 = 3;
+	pub const CommitThreshold: Perbill = Perbill::from_percent(0);
+	pub const HeartbeatInterval: u64 = 0;
+	pub const DeduplicateMessages: bool = false;
+	pub const CommitmentHistorySize: u64 = 16;
 }
 
 impl snowbridge_basic_channel::outbound::Config for Test {
@@ -158,6 +164,10 @@ impl snowbridge_basic_channel::outbound::Config for Test {
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type CommitThreshold = CommitThreshold;
+	type HeartbeatInterval = HeartbeatInterval;
+	type DeduplicateMessages = DeduplicateMessages;
+	type CommitmentHistorySize = CommitmentHistorySize;
 	type SetPrincipalOrigin = frame_system::EnsureRoot<AccountId>;
 	type WeightInfo = ();
 }
@@ -192,6 +202,7 @@ impl XcmReserveTransfer<AccountId, Origin> for XcmAssetTransfererMock<Test> {
 parameter_types! {
 	pub const EtherAssetId: u128 = 0;
 	pub const EtherAppPalletId: PalletId = PalletId(*b"etherapp");
+	pub storage EtherDecimalsConversion: DecimalsConversion = DecimalsConversion::IDENTITY;
 }
 
 pub type Ether = ItemOf<Assets, EtherAssetId, AccountId>;
@@ -204,6 +215,7 @@ impl crate::Config for Test {
 	type XcmReserveTransfer = XcmAssetTransfererMock<Self>;
 	type CallOrigin = snowbridge_dispatch::EnsureEthereumAccount;
 	type WeightInfo = ();
+	type DecimalsConversion = EtherDecimalsConversion;
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -1,4 +1,7 @@
-use crate::mock::{new_tester, AccountId, Ether, EtherApp, Event, Origin, System, Test};
+use crate::mock::{
+	new_tester, AccountId, Ether, EtherApp, EtherDecimalsConversion, Event, Origin, System, Test,
+};
+use crate::DecimalsConversion;
 use frame_support::{
 	assert_noop, assert_ok,
 	traits::fungible::{Inspect, Mutate},
@@ -108,3 +111,241 @@ fn should_not_burn_on_commitment_failure() {
 		);
 	});
 }
+
+#[test]
+fn should_keep_balance_on_commitment_failure() {
+	new_tester().execute_with(|| {
+		let sender: AccountId = Keyring::Bob.into();
+		let recipient = H160::repeat_byte(9);
+
+		Ether::mint_into(&sender, 500).unwrap();
+
+		// fill up message queue
+		for _ in 0..3 {
+			assert_ok!(EtherApp::burn(
+				Origin::signed(sender.clone()),
+				ChannelId::Incentivized,
+				recipient.clone(),
+				20,
+			));
+		}
+		assert_eq!(Ether::balance(&sender), 440);
+
+		assert!(EtherApp::burn(
+			Origin::signed(sender.clone()),
+			ChannelId::Incentivized,
+			recipient.clone(),
+			20
+		)
+		.is_err());
+		assert_eq!(Ether::balance(&sender), 440);
+	});
+}
+
+#[test]
+fn mint_and_burn_scale_by_decimals_conversion() {
+	new_tester().execute_with(|| {
+		// ETH has 18 decimals, the local asset 12.
+		EtherDecimalsConversion::set(&DecimalsConversion { multiplier: 1, divisor: 1_000_000 });
+
+		let peer_contract = H160::repeat_byte(1);
+		let sender = H160::repeat_byte(7);
+		let recipient: AccountId = Keyring::Bob.into();
+
+		assert_ok!(EtherApp::mint(
+			snowbridge_dispatch::RawOrigin(peer_contract).into(),
+			sender,
+			recipient.clone(),
+			2_500_000_000_000_000_000,
+			None,
+		));
+		assert_eq!(Ether::balance(&recipient), 2_500_000_000_000);
+		assert_eq!(
+			Event::EtherApp(crate::Event::<Test>::Minted(sender, recipient.clone(), 2_500_000_000_000)),
+			last_event()
+		);
+
+		assert_ok!(EtherApp::burn(
+			Origin::signed(recipient.clone()),
+			ChannelId::Incentivized,
+			H160::repeat_byte(9),
+			500_000_000_000,
+		));
+		assert_eq!(Ether::balance(&recipient), 2_000_000_000_000);
+	});
+}
+
+#[test]
+fn mint_and_burn_reject_conversion_overflow() {
+	new_tester().execute_with(|| {
+		EtherDecimalsConversion::set(&DecimalsConversion { multiplier: 1_000_000, divisor: 1 });
+
+		let peer_contract = H160::repeat_byte(1);
+		let recipient: AccountId = Keyring::Bob.into();
+
+		assert_noop!(
+			EtherApp::mint(
+				snowbridge_dispatch::RawOrigin(peer_contract).into(),
+				H160::repeat_byte(7),
+				recipient.clone(),
+				u128::MAX,
+				None,
+			),
+			crate::Error::<Test>::ConversionOverflow
+		);
+
+		EtherDecimalsConversion::set(&DecimalsConversion { multiplier: 1, divisor: 1_000_000 });
+		Ether::mint_into(&recipient, u128::MAX).unwrap();
+
+		assert_noop!(
+			EtherApp::burn(Origin::signed(recipient), ChannelId::Incentivized, H160::repeat_byte(9), u128::MAX),
+			crate::Error::<Test>::ConversionOverflow
+		);
+	});
+}
+
+#[test]
+fn mint_rounds_down_and_records_dust() {
+	new_tester().execute_with(|| {
+		EtherDecimalsConversion::set(&DecimalsConversion { multiplier: 1, divisor: 1_000_000 });
+
+		let peer_contract = H160::repeat_byte(1);
+		let recipient: AccountId = Keyring::Bob.into();
+
+		assert_ok!(EtherApp::mint(
+			snowbridge_dispatch::RawOrigin(peer_contract).into(),
+			H160::repeat_byte(7),
+			recipient.clone(),
+			2_000_001,
+			None,
+		));
+		assert_ok!(EtherApp::mint(
+			snowbridge_dispatch::RawOrigin(peer_contract).into(),
+			H160::repeat_byte(7),
+			recipient.clone(),
+			999_999,
+			None,
+		));
+
+		assert_eq!(Ether::balance(&recipient), 2);
+		assert_eq!(EtherApp::minted_supply(), 2);
+		assert_eq!(EtherApp::unminted_dust(), 1_000_000);
+	});
+}
+
+#[test]
+fn burn_leaves_remainder_smaller_than_a_wei() {
+	new_tester().execute_with(|| {
+		// The local asset has 6 more decimals than ETH.
+		EtherDecimalsConversion::set(&DecimalsConversion { multiplier: 1_000_000, divisor: 1 });
+
+		let recipient: AccountId = Keyring::Bob.into();
+
+		assert_ok!(EtherApp::mint(
+			snowbridge_dispatch::RawOrigin(H160::repeat_byte(1)).into(),
+			H160::repeat_byte(7),
+			recipient.clone(),
+			3,
+			None,
+		));
+		assert_eq!(Ether::balance(&recipient), 3_000_000);
+
+		assert_ok!(EtherApp::burn(
+			Origin::signed(recipient.clone()),
+			ChannelId::Incentivized,
+			H160::repeat_byte(9),
+			2_500_000,
+		));
+		assert_eq!(Ether::balance(&recipient), 1_000_000);
+		assert_eq!(EtherApp::minted_supply(), 1_000_000);
+		assert_eq!(
+			Event::EtherApp(crate::Event::<Test>::Burned(recipient, H160::repeat_byte(9), 2_000_000)),
+			last_event()
+		);
+	});
+}
+
+#[test]
+fn minted_supply_converts_back_to_wei() {
+	new_tester().execute_with(|| {
+		EtherDecimalsConversion::set(&DecimalsConversion { multiplier: 1, divisor: 1_000_000 });
+
+		assert_ok!(EtherApp::mint(
+			snowbridge_dispatch::RawOrigin(H160::repeat_byte(1)).into(),
+			H160::repeat_byte(7),
+			Keyring::Bob.into(),
+			3_000_000,
+			None,
+		));
+		assert_eq!(EtherApp::minted_supply(), 3);
+		assert_eq!(EtherApp::minted_supply_in_wei(), Some(3_000_000));
+	});
+}
+
+#[test]
+fn mint_and_burn_track_minted_supply() {
+	new_tester().execute_with(|| {
+		let peer_contract = H160::repeat_byte(1);
+		let sender = H160::repeat_byte(7);
+		let bob: AccountId = Keyring::Bob.into();
+
+		assert_ok!(EtherApp::mint(
+			snowbridge_dispatch::RawOrigin(peer_contract).into(),
+			sender,
+			bob.clone(),
+			500,
+			None,
+		));
+		assert_eq!(EtherApp::minted_supply(), 500);
+
+		assert_ok!(EtherApp::burn(
+			Origin::signed(bob.clone()),
+			ChannelId::Incentivized,
+			H160::repeat_byte(2),
+			200
+		));
+		assert_eq!(EtherApp::minted_supply(), 300);
+	});
+}
+
+#[test]
+fn burn_exceeding_minted_supply_does_not_underflow() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+
+		// Balance that didn't come through the bridge, e.g. from genesis.
+		Ether::mint_into(&bob, 500).unwrap();
+
+		assert_ok!(EtherApp::burn(
+			Origin::signed(bob.clone()),
+			ChannelId::Incentivized,
+			H160::repeat_byte(2),
+			20
+		));
+		assert_eq!(EtherApp::minted_supply(), 0);
+	});
+}
+
+#[test]
+fn migrate_to_v1_seeds_minted_supply() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_tester().execute_with(|| {
+		StorageVersion::new(0).put::<EtherApp>();
+		// ETH bridged before the supply was tracked.
+		Ether::mint_into(&Keyring::Bob.into(), 500).unwrap();
+		Ether::mint_into(&Keyring::Alice.into(), 300).unwrap();
+
+		assert_ok!(crate::migration::v1::pre_migrate::<Test>());
+		<EtherApp as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_ok!(crate::migration::v1::post_migrate::<Test>());
+
+		assert_eq!(EtherApp::on_chain_storage_version(), 1);
+		assert_eq!(EtherApp::minted_supply(), 800);
+
+		// Running again is a no-op.
+		Ether::mint_into(&Keyring::Bob.into(), 100).unwrap();
+		<EtherApp as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(EtherApp::minted_supply(), 800);
+	});
+}
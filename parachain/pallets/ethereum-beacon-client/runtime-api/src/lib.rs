@@ -0,0 +1,58 @@
+//! Runtime API for the Ethereum beacon client, used by relayers to query light client state.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_core::H256;
+use sp_std::vec::Vec;
+
+use snowbridge_ethereum_beacon_client::{
+	ChainConfig, HeaderStatus, LightClientSnapshot, PeriodContext, SyncCommittee,
+};
+
+sp_api::decl_runtime_apis! {
+	pub trait EthereumBeaconClientApi {
+		/// The sync committee governing `slot`, if it is known.
+		fn sync_committee_for_slot(slot: u64) -> Option<SyncCommittee>;
+
+		/// Whether a finalized header has been imported for `slot`.
+		fn header_status(slot: u64) -> HeaderStatus;
+
+		/// The sync committee period the next period update should be for.
+		fn next_expected_period() -> u64;
+
+		/// The sync committee periods used to verify a header at `slot`.
+		fn period_context(slot: u64) -> PeriodContext;
+
+		/// The sync committee period of the latest finalized header, if any.
+		fn current_period() -> Option<u64>;
+
+		/// The sync committee for the period after the current one, if it is known.
+		fn next_sync_committee() -> Option<SyncCommittee>;
+
+		/// Slots elapsed from the latest finalized header to `current_slot`, if any header has
+		/// been imported.
+		fn slots_since_last_finalized(current_slot: u64) -> Option<u64>;
+
+		/// Whether the header with hash tree root `block_root` is finalized.
+		fn is_finalized(block_root: H256) -> bool;
+
+		/// The state needed to restore the light client elsewhere, if it has been initialized.
+		fn export_state() -> Option<LightClientSnapshot>;
+
+		/// The genesis validators root and chain parameters headers are verified against.
+		fn chain_config() -> ChainConfig;
+
+		/// Hash tree root of `committee`, for comparison against the leaf of a sync committee
+		/// Merkle proof, or `None` if it can't be merkleized.
+		fn sync_committee_root(committee: SyncCommittee) -> Option<H256>;
+
+		/// Whether `branch` proves `committee` against `state_root` at the given depth and index.
+		fn check_sync_committee_proof(
+			committee: SyncCommittee,
+			branch: Vec<H256>,
+			state_root: H256,
+			depth: u64,
+			index: u64,
+		) -> bool;
+	}
+}
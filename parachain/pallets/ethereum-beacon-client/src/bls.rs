@@ -0,0 +1,60 @@
+//! BLS signature verification, behind a trait so that an alternative implementation (e.g. one
+//! backed by host functions) can be configured instead of the default milagro one.
+
+use crate::PublicKey;
+use milagro_bls::{AggregatePublicKey, AggregateSignature, AmclError, Signature};
+use sp_core::H256;
+use sp_std::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BlsError {
+	/// The signature could not be decoded.
+	InvalidSignature,
+	/// A public key is not a valid curve point.
+	InvalidSignaturePoint,
+	/// The public keys could not be aggregated.
+	InvalidAggregatePublicKeys,
+	/// The signature does not match the message and public keys.
+	SignatureVerificationFailed,
+}
+
+pub trait BlsVerify {
+	/// Verifies `signature` is an aggregate signature over `message` by all of `pubkeys`.
+	fn fast_aggregate_verify(
+		pubkeys: &[PublicKey],
+		message: H256,
+		signature: &[u8],
+	) -> Result<(), BlsError>;
+}
+
+/// Verification using the `milagro_bls` crate.
+pub struct MilagroBls;
+
+impl BlsVerify for MilagroBls {
+	fn fast_aggregate_verify(
+		pubkeys: &[PublicKey],
+		message: H256,
+		signature: &[u8],
+	) -> Result<(), BlsError> {
+		let sig = Signature::from_bytes(signature).map_err(|_| BlsError::InvalidSignature)?;
+		let agg_sig = AggregateSignature::from_signature(&sig);
+
+		let public_keys = pubkeys
+			.iter()
+			.map(|bytes| milagro_bls::PublicKey::from_bytes_unchecked(&bytes.0))
+			.collect::<Result<Vec<milagro_bls::PublicKey>, _>>()
+			.map_err(|e| match e {
+				AmclError::InvalidPoint => BlsError::InvalidSignaturePoint,
+				_ => BlsError::InvalidSignature,
+			})?;
+
+		let agg_pub_key = AggregatePublicKey::into_aggregate(&public_keys)
+			.map_err(|_| BlsError::InvalidAggregatePublicKeys)?;
+
+		if !agg_sig.fast_aggregate_verify_pre_aggregated(message.as_bytes(), &agg_pub_key) {
+			return Err(BlsError::SignatureVerificationFailed)
+		}
+
+		Ok(())
+	}
+}
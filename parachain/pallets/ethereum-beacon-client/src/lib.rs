@@ -1,21 +1,30 @@
 //! # Ethereum Beacon Client
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod bls;
 mod merklization;
+pub mod metrics;
+pub mod migration;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 
 use codec::{Decode, Encode};
-use frame_support::{dispatch::DispatchResult, log, transactional};
-use frame_system::ensure_signed;
+use frame_support::{
+	dispatch::DispatchResult, log, storage::with_transaction, traits::UnixTime, transactional,
+};
 use scale_info::TypeInfo;
 use sp_core::H256;
-use sp_io::hashing::sha2_256;
-use sp_runtime::RuntimeDebug;
+use sp_io::hashing::{blake2_256, sha2_256};
+use sp_runtime::{DispatchError, Perbill, RuntimeDebug, TransactionOutcome};
 use sp_std::prelude::*;
 
+use snowbridge_core::{BeaconProof, BeaconVerifier, Message, RewardHeaderImport};
+
+pub use bls::{BlsError, BlsVerify, MilagroBls};
+use snowbridge_ethereum::Log;
+
 type Root = H256;
 type Domain = H256;
 type ValidatorIndex = u64;
@@ -35,13 +44,114 @@ const NEXT_SYNC_COMMITTEE_INDEX: u64 = 23;
 const FINALIZED_ROOT_DEPTH: u64 = 6;
 const FINALIZED_ROOT_INDEX: u64 = 41;
 
-/// GENESIS_FORK_VERSION('0x00000000')
-const GENESIS_FORK_VERSION: ForkVersion = [30, 30, 30, 30];
+/// From Electra the beacon state has more than 32 fields, which adds a level to its
+/// Merkle tree and shifts the generalized indices of its fields.
+const CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA: u64 = 86;
+const CURRENT_SYNC_COMMITTEE_DEPTH_ELECTRA: u64 = 6;
+
+const NEXT_SYNC_COMMITTEE_INDEX_ELECTRA: u64 = 87;
+const NEXT_SYNC_COMMITTEE_DEPTH_ELECTRA: u64 = 6;
+
+const FINALIZED_ROOT_INDEX_ELECTRA: u64 = 169;
+const FINALIZED_ROOT_DEPTH_ELECTRA: u64 = 7;
+
+/// Number of validators in a sync committee. Everything that depends on the committee size,
+/// from bit vector lengths to merkleization, must use this.
+const SYNC_COMMITTEE_SIZE: usize = 512;
+
+/// Position of the execution block hash within a beacon block body, i.e. of `block_hash`
+/// within `execution_payload`, for the Bellatrix and Capella block layouts.
+const EXECUTION_BLOCK_HASH_INDEX: u64 = 412;
+const EXECUTION_BLOCK_HASH_DEPTH: u64 = 8;
+
+/// As above, from Deneb on. The execution payload grows to 17 fields, adding a level.
+const EXECUTION_BLOCK_HASH_INDEX_DENEB: u64 = 812;
+const EXECUTION_BLOCK_HASH_DEPTH_DENEB: u64 = 9;
+
+const SIGNATURE_SIZE: usize = 96;
+
+/// Weight of verifying a sync committee signature, which dominates the weight of a header
+/// import. Charged again when a header near a period boundary is retried against the next
+/// period's committee.
+const SIGNATURE_VERIFICATION_WEIGHT: u64 = 1_000_000;
+
+/// Compressed encoding of the BLS12-381 G1 point at infinity: the compression and infinity flags
+/// set, with every other bit zero.
+const G1_POINT_AT_INFINITY: [u8; 48] = {
+	let mut point = [0u8; 48];
+	point[0] = 0xc0;
+	point
+};
+
+/// Number of recently verified sync committee signatures remembered to skip re-verification.
+const VERIFIED_SIGNATURE_CACHE_SIZE: usize = 8;
 
 /// DomainType('0x07000000')
 /// https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/beacon-chain.md#domain-types
 const DOMAIN_SYNC_COMMITTEE: [u8; 4] = [7, 0, 0, 0];
 
+/// A beacon chain slot. SCALE encodes identically to the underlying `u64`.
+#[derive(
+	Clone, Copy, Default, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug, TypeInfo,
+)]
+pub struct Slot(pub u64);
+
+impl Slot {
+	/// Epoch this slot falls in.
+	pub fn epoch(&self) -> u64 {
+		self.0 / SLOTS_PER_EPOCH
+	}
+
+	/// Sync committee period this slot falls in.
+	pub fn sync_period(&self) -> u64 {
+		self.0 / slots_per_period()
+	}
+}
+
+/// Number of slots in a sync committee period.
+pub const fn slots_per_period() -> u64 {
+	SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+/// First slot of sync committee period `period`.
+pub fn period_start_slot(period: u64) -> Slot {
+	Slot(period.saturating_mul(slots_per_period()))
+}
+
+/// Last slot of sync committee period `period`.
+pub fn period_end_slot(period: u64) -> Slot {
+	Slot(period.saturating_add(1).saturating_mul(slots_per_period()).saturating_sub(1))
+}
+
+/// The sync committee periods that may be used to verify a header signed at `slot`.
+pub fn period_context(slot: Slot) -> PeriodContext {
+	let period = slot.sync_period();
+	PeriodContext {
+		period,
+		start_slot: period_start_slot(period),
+		end_slot: period_end_slot(period),
+		adjacent_period: period.saturating_add(1),
+	}
+}
+
+impl From<u64> for Slot {
+	fn from(slot: u64) -> Self {
+		Slot(slot)
+	}
+}
+
+impl From<Slot> for u64 {
+	fn from(slot: Slot) -> Self {
+		slot.0
+	}
+}
+
+impl sp_std::fmt::Display for Slot {
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
 #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct PublicKey([u8; 48]);
 
@@ -51,12 +161,40 @@ impl Default for PublicKey {
 	}
 }
 
+#[derive(Clone, Copy, PartialEq, RuntimeDebug)]
+pub enum PublicKeyError {
+	/// The input is not 48 bytes long.
+	InvalidLength,
+	/// The bytes do not encode a valid compressed BLS public key.
+	InvalidPoint,
+	/// The input is not valid hex.
+	InvalidHex,
+}
+
+impl PublicKey {
+	/// Creates a public key from its 48 byte compressed encoding, checking that it is a valid
+	/// point on the curve.
+	pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey, PublicKeyError> {
+		let key: [u8; 48] = bytes.try_into().map_err(|_| PublicKeyError::InvalidLength)?;
+		milagro_bls::PublicKey::from_bytes(&key).map_err(|_| PublicKeyError::InvalidPoint)?;
+
+		Ok(PublicKey(key))
+	}
+
+	/// Parses a public key from hex, with or without a `0x` prefix.
+	#[cfg(feature = "std")]
+	pub fn from_hex(hex: &str) -> Result<PublicKey, PublicKeyError> {
+		let bytes = sp_core::bytes::from_hex(hex).map_err(|_| PublicKeyError::InvalidHex)?;
+		Self::from_bytes(&bytes)
+	}
+}
+
 /// Beacon block header as it is stored in the runtime storage. The block root is the
 /// Merklization of a BeaconHeader.
 #[derive(Clone, Default, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct BeaconBlockHeader {
 	// The slot for which this block is created. Must be greater than the slot of the block defined by parentRoot.
-	pub slot: u64,
+	pub slot: Slot,
 	// The index of the validator that proposed the block.
 	pub proposer_index: ValidatorIndex,
 	// The block root of the parent block, forming a block chain.
@@ -74,10 +212,21 @@ pub struct SyncCommittee {
 	pub aggregate_pubkey: PublicKey,
 }
 
+/// Sync committee participation in its packed SSZ form: one bit per committee member, in
+/// committee order, least significant bit first within each byte.
+#[derive(Clone, Default, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct SyncCommitteeBits(pub Vec<u8>);
+
+impl SyncCommitteeBits {
+	/// Participation of each committee member, in committee order, as `1` or `0`.
+	pub fn iter_bits(&self) -> impl Iterator<Item = u8> + '_ {
+		self.0.iter().flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1))
+	}
+}
+
 #[derive(Clone, Default, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct SyncAggregate {
-	// 1 or 0 bit, indicates whether a sync committee participated in a vote
-	pub sync_committee_bits: Vec<u8>,
+	pub sync_committee_bits: SyncCommitteeBits,
 	pub sync_committee_signature: Vec<u8>,
 }
 
@@ -128,6 +277,94 @@ pub struct Genesis {
 	pub validators_root: Root,
 }
 
+/// Position of a beacon state field in the state's Merkle tree.
+#[derive(Clone, Copy, PartialEq, RuntimeDebug)]
+pub struct GeneralizedIndex {
+	pub index: u64,
+	pub depth: u64,
+}
+
+/// Positions of the beacon state fields the light client proves, for a given fork.
+#[derive(Clone, Copy, PartialEq, RuntimeDebug)]
+pub struct GeneralizedIndices {
+	pub current_sync_committee: GeneralizedIndex,
+	pub next_sync_committee: GeneralizedIndex,
+	pub finalized_root: GeneralizedIndex,
+}
+
+/// Import status of the beacon header at a given slot.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum HeaderStatus {
+	/// No header has been imported for the slot.
+	Unknown,
+	/// A finalized header has been verified and stored for the slot.
+	Finalized,
+}
+
+/// The state the light client needs to resume importing headers, for operators to checkpoint
+/// and restore it.
+#[derive(Clone, Default, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct LightClientSnapshot {
+	pub genesis_validators_root: H256,
+	pub latest_finalized_header: BeaconBlockHeader,
+	/// Sync committee of the latest finalized header's period.
+	pub current_sync_committee: SyncCommittee,
+	/// Sync committee of the following period, if it is known.
+	pub next_sync_committee: Option<SyncCommittee>,
+}
+
+/// The parameters headers are verified against, for relayers to configure themselves from.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ChainConfig {
+	pub genesis_validators_root: H256,
+	pub slots_per_epoch: u64,
+	pub epochs_per_period: u64,
+	/// Epoch from which Electra beacon state proof indices are used.
+	pub electra_fork_epoch: u64,
+	/// Epoch from which Deneb execution payload proof indices are used.
+	pub deneb_fork_epoch: u64,
+	/// Fork versions for computing signature domains.
+	pub fork_versions: ForkVersions,
+}
+
+/// Fork versions of the Ethereum network being followed.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ForkVersions {
+	pub genesis: ForkVersion,
+	/// Version from `deneb_fork_epoch`.
+	pub deneb: ForkVersion,
+	/// Version from `electra_fork_epoch`.
+	pub electra: ForkVersion,
+}
+
+/// The sync committee period of a slot and the period tried when verification against it fails.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct PeriodContext {
+	pub period: u64,
+	pub start_slot: Slot,
+	pub end_slot: Slot,
+	/// Period whose committee is tried if the header's signature doesn't verify against
+	/// `period`'s committee.
+	pub adjacent_period: u64,
+}
+
+/// A finalized header update whose sync committee signature failed to verify.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct FailedVerification<AccountId> {
+	/// Slot of the finalized header in the update.
+	pub slot: Slot,
+	/// Account that submitted the update.
+	pub relayer: AccountId,
+	pub reason: DispatchError,
+}
+
+/// Sync committee participation observed across the finalized headers of a period.
+#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ParticipationStats {
+	pub headers: u64,
+	pub participants: u64,
+}
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -138,19 +375,119 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
-	use milagro_bls::{AggregatePublicKey, AggregateSignature, AmclError, Signature};
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(migration::STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Determines whether stored finalized headers whose parent is stored are checked for
+		/// slot ordering against it and against the latest finalized header.
+		#[pallet::constant]
+		type VerifyParentLinkage: Get<bool>;
+		/// Whether finalized header updates must finalize an epoch boundary slot. Finalized
+		/// checkpoints normally are, but a skipped boundary slot moves the checkpoint block
+		/// earlier, so this should only be enabled where that is not expected.
+		#[pallet::constant]
+		type RequireEpochAlignedFinalizedSlot: Get<bool>;
+		/// Whether a finalized header update may skip sync committee signature verification
+		/// when its attested header's signing root was verified recently and its finalized
+		/// header links to a stored header. This trades security for latency: the attested
+		/// header is known to be signed, but the update's participation bits are trusted as
+		/// submitted.
+		#[pallet::constant]
+		type ReuseVerifiedSigningRoots: Get<bool>;
+		/// Epoch at which the Electra fork activates, changing beacon state proof indices.
+		#[pallet::constant]
+		type ElectraForkEpoch: Get<u64>;
+		/// Epoch at which the Deneb fork activates, changing execution payload proof indices.
+		#[pallet::constant]
+		type DenebForkEpoch: Get<u64>;
+		/// Fork version at genesis of the Ethereum network being followed, used when computing
+		/// a domain without an explicit fork version. `0x00000000` on mainnet.
+		#[pallet::constant]
+		type GenesisForkVersion: Get<ForkVersion>;
+		/// Fork version from `DenebForkEpoch`, reported to relayers in [`ChainConfig`].
+		#[pallet::constant]
+		type DenebForkVersion: Get<ForkVersion>;
+		/// Fork version from `ElectraForkEpoch`, reported to relayers in [`ChainConfig`].
+		#[pallet::constant]
+		type ElectraForkVersion: Get<ForkVersion>;
+		/// Source of the current time, used to estimate how far behind the light client is.
+		type TimeProvider: UnixTime;
+		/// Number of slots the light client may fall behind before a warning is emitted.
+		/// Should be well within the weak subjectivity period.
+		#[pallet::constant]
+		type FallingBehindThreshold: Get<u64>;
+		/// Unix time in seconds of the beacon chain's genesis.
+		#[pallet::constant]
+		type GenesisTime: Get<u64>;
+		/// Duration of a beacon chain slot in seconds.
+		#[pallet::constant]
+		type SecondsPerSlot: Get<u64>;
+		/// Number of slots a finalized header may be ahead of the slot expected from the current
+		/// time, allowing for clock drift between the parachain and the beacon chain.
+		#[pallet::constant]
+		type MaxFutureSlots: Get<u64>;
+		/// Number of slots at the end of a sync committee period within which an attested
+		/// header may have been signed by the next period's committee, which is then tried
+		/// if the current one fails.
+		#[pallet::constant]
+		type PeriodBoundarySlots: Get<u64>;
+		/// Highest proposer index accepted in a submitted header, comfortably above the size of
+		/// the validator set.
+		#[pallet::constant]
+		type MaxProposerIndex: Get<u64>;
+		/// Maximum number of nodes accepted in a submitted Merkle proof branch.
+		#[pallet::constant]
+		type MaxProofBranchLength: Get<u32>;
+		/// Number of failed finalized header signature verifications kept for later
+		/// investigation. The oldest entry is evicted once the limit is reached.
+		#[pallet::constant]
+		type MaxFailedVerifications: Get<u32>;
+		/// Implementation of BLS signature verification.
+		type BlsVerifier: BlsVerify;
+		/// The origin which may perform administrative recovery actions.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+		/// The origin which may submit beacon headers and sync committee updates.
+		type HeaderSubmissionOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+		/// Pays relayers for each finalized header they import.
+		type RelayerRewards: RewardHeaderImport<Self::AccountId>;
 	}
 
 	#[pallet::event]
-	pub enum Event<T> {}
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A finalized header was stored whose stored parent is inconsistent with the slots of
+		/// the stored headers. \[slot, block_root\]
+		HeaderChainBroken(Slot, H256),
+		/// No finalized header has been imported for an estimated `slots` slots.
+		FallingBehind { slots: u64 },
+		/// The genesis validators root was updated by the admin origin.
+		/// \[validators_root\]
+		GenesisRootUpdated(H256),
+		/// A sync committee was stored by the admin origin without a period update proof.
+		/// \[period\]
+		ForcedCommitteeStored(u64),
+		/// Light client state was restored from a snapshot by the admin origin.
+		/// \[slot\]
+		SnapshotImported(Slot),
+		/// A batch of sync committee period updates was processed, of which the first
+		/// `count` were applied.
+		SyncCommitteePeriodUpdatesApplied { count: u32 },
+		/// The sync committee stored for `period` lists `duplicates` public keys more than
+		/// once. The spec allows this, but it is rare enough to warrant a look.
+		SuspiciousCommittee { period: u64, duplicates: u32 },
+		/// A verified finalized header differed from the one already stored for its slot, and
+		/// was not imported.
+		FinalizedHeaderConflict { slot: Slot },
+		/// Importing the finalized header at `slot` failed. If `retryable`, the same update may
+		/// succeed later, e.g. once its sync committee is installed; otherwise it never will.
+		FinalizedHeaderImportFailed { slot: Slot, retryable: bool },
+	}
 
 	#[pallet::error]
 	pub enum Error<T> {
@@ -159,6 +496,7 @@ pub mod pallet {
 		SyncCommitteeMissing,
 		Unknown,
 		SyncCommitteeParticipantsNotSupermajority,
+		InsufficientSyncCommitteeParticipants,
 		InvalidSyncCommiteeSignature,
 		InvalidHeaderMerkleProof,
 		InvalidSyncCommitteeMerkleProof,
@@ -168,10 +506,66 @@ pub mod pallet {
 		InvalidHash,
 		SignatureVerificationFailed,
 		NoBranchExpected,
+		InvalidDomainType,
+		InvalidSyncAggregate,
+		InvalidSyncCommitteeBitsLength,
+		InvalidSyncCommitteeBits,
+		AlreadyImported,
+		MalformedProofBranch,
+		ProofBranchTooLong,
+		StaleOptimisticHeader,
+		InvalidProposerIndex,
+		DegenerateHeader,
+		InvalidSyncCommitteeSize,
+		MissingHeader,
+		InvalidProof,
+		DecodeFailed,
+		UnalignedFinalizedSlot,
+		HeaderFromFuture,
+		FinalizedHeaderNotBeforeAttested,
+		FinalizedHeaderConflict,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		// Warn once when no finalized header has been imported for longer than the
+		// configured threshold, so operators can fix relaying before a checkpoint
+		// resync is required.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			if <FallingBehindReported<T>>::get() {
+				return T::DbWeight::get().reads(1)
+			}
+
+			if let Some(slots) = Self::slots_behind_estimate() {
+				if slots > T::FallingBehindThreshold::get() {
+					<FallingBehindReported<T>>::put(true);
+					Self::deposit_event(Event::FallingBehind { slots });
+					return T::DbWeight::get().reads_writes(2, 1)
+				}
+			}
+
+			T::DbWeight::get().reads(2)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migration::v1::migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migration::v1::pre_migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migration::v1::post_migrate::<T>()
+		}
+	}
+
+	/// Most recent header signed by the sync committee, imported without a finality proof.
+	#[pallet::storage]
+	#[pallet::getter(fn optimistic_header)]
+	pub(super) type OptimisticHeader<T: Config> = StorageValue<_, BeaconBlockHeader, OptionQuery>;
 
 	#[pallet::storage]
 	pub(super) type FinalizedHeaders<T: Config> =
@@ -179,14 +573,58 @@ pub mod pallet {
 
 	#[pallet::storage]
 	pub(super) type FinalizedHeadersBySlot<T: Config> =
-		StorageMap<_, Identity, u64, H256, OptionQuery>;
+		StorageMap<_, Identity, Slot, H256, OptionQuery>;
 
 	/// Current sync committee corresponding to the active header.
-	/// TODO  prune older sync committees than xxx
+	/// TODO  prune older sync committees than xxx. Headers are verified in the extrinsic that
+	/// submits them rather than staged, so no pending header can depend on a pruned committee.
 	#[pallet::storage]
 	pub(super) type SyncCommittees<T: Config> =
 		StorageMap<_, Identity, u64, SyncCommittee, ValueQuery>;
 
+	/// Highest sync committee period for which a committee has been stored.
+	#[pallet::storage]
+	#[pallet::getter(fn highest_stored_period)]
+	pub(super) type HighestStoredPeriod<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// Slot of the most recent finalized header stored.
+	#[pallet::storage]
+	#[pallet::getter(fn latest_finalized_slot)]
+	pub(super) type LatestFinalizedSlot<T: Config> = StorageValue<_, Slot, OptionQuery>;
+
+	/// Number of finalized headers stored per sync committee period.
+	#[pallet::storage]
+	pub(super) type FinalizedHeaderCountByPeriod<T: Config> =
+		StorageMap<_, Identity, u64, u32, ValueQuery>;
+
+	/// Unix time, in seconds, at which the latest finalized header was imported.
+	#[pallet::storage]
+	pub(super) type LatestFinalizedImportTime<T: Config> = StorageValue<_, u64, OptionQuery>;
+
+	/// Whether the light client falling behind has been reported since the last import.
+	#[pallet::storage]
+	pub(super) type FallingBehindReported<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Sync committee participation per sync committee period.
+	#[pallet::storage]
+	pub(super) type ParticipationStatsByPeriod<T: Config> =
+		StorageMap<_, Identity, u64, ParticipationStats, ValueQuery>;
+
+	/// Keys of recently verified sync committee signatures, oldest first.
+	#[pallet::storage]
+	pub(super) type VerifiedSignatures<T: Config> = StorageValue<_, Vec<H256>, ValueQuery>;
+
+	/// Signing roots of recently verified attested headers, oldest first. Only kept when
+	/// [`Config::ReuseVerifiedSigningRoots`] is enabled.
+	#[pallet::storage]
+	pub(super) type VerifiedSigningRoots<T: Config> = StorageValue<_, Vec<H256>, ValueQuery>;
+
+	/// Finalized header updates that failed signature verification, oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn failed_verifications)]
+	pub(super) type FailedVerifications<T: Config> =
+		StorageValue<_, Vec<FailedVerification<T::AccountId>>, ValueQuery>;
+
 	#[pallet::storage]
 	pub(super) type ChainGenesis<T: Config> = StorageValue<_, Genesis, ValueQuery>;
 
@@ -213,7 +651,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			initial_sync: InitialSync,
 		) -> DispatchResult {
-			let _sender = ensure_signed(origin)?;
+			let _sender = T::HeaderSubmissionOrigin::ensure_origin(origin)?;
 
 			log::trace!(
 				target: "ethereum-beacon-client",
@@ -243,7 +681,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			sync_committee_period_update: SyncCommitteePeriodUpdate,
 		) -> DispatchResult {
-			let _sender = ensure_signed(origin)?;
+			let _sender = T::HeaderSubmissionOrigin::ensure_origin(origin)?;
 
 			let sync_committee_period = sync_committee_period_update.sync_committee_period;
 			log::trace!(
@@ -270,13 +708,56 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(1_000_000)]
-		#[transactional]
+		/// Applies consecutive sync committee period updates in ascending period order, so a
+		/// relayer can catch up on several periods in one transaction. Each update is verified
+		/// against the committee installed by the one before it. Processing stops at the first
+		/// update that fails, keeping the ones already applied; the call only fails if none
+		/// could be applied.
+		#[pallet::weight((updates.len() as Weight).saturating_mul(1_000_000))]
+		pub fn sync_committee_period_updates(
+			origin: OriginFor<T>,
+			mut updates: Vec<SyncCommitteePeriodUpdate>,
+		) -> DispatchResultWithPostInfo {
+			let _sender = T::HeaderSubmissionOrigin::ensure_origin(origin)?;
+
+			updates.sort_by_key(|update| update.sync_committee_period);
+
+			let mut applied: u32 = 0;
+			for update in updates {
+				let sync_committee_period = update.sync_committee_period;
+				let result = with_transaction(|| {
+					match Self::process_sync_committee_period_update(update) {
+						Ok(()) => TransactionOutcome::Commit(Ok(())),
+						Err(err) => TransactionOutcome::Rollback(Err(err)),
+					}
+				});
+
+				if let Err(err) = result {
+					log::error!(
+						target: "ethereum-beacon-client",
+						"Sync committee period update for period {} failed with error {:?}",
+						sync_committee_period,
+						err
+					);
+					if applied == 0 {
+						return Err(err.into());
+					}
+					break;
+				}
+				applied += 1;
+			}
+
+			Self::deposit_event(Event::SyncCommitteePeriodUpdatesApplied { count: applied });
+
+			Ok(Some((applied as Weight).saturating_mul(1_000_000)).into())
+		}
+
+		#[pallet::weight(SIGNATURE_VERIFICATION_WEIGHT.saturating_mul(2))]
 		pub fn import_finalized_header(
 			origin: OriginFor<T>,
 			finalized_header_update: FinalizedHeaderUpdate,
-		) -> DispatchResult {
-			let _sender = ensure_signed(origin)?;
+		) -> DispatchResultWithPostInfo {
+			let relayer = T::HeaderSubmissionOrigin::ensure_origin(origin)?;
 
 			let slot = finalized_header_update.finalized_header.slot;
 
@@ -286,13 +767,42 @@ pub mod pallet {
 				slot
 			);
 
-			if let Err(err) = Self::process_finalized_header(finalized_header_update) {
-				log::error!(
-					target: "ethereum-beacon-client",
-					"Finalized header update failed with error {:?}",
-					err
-				);
-				return Err(err);
+			let previous_latest_slot = <LatestFinalizedSlot<T>>::get();
+
+			// Roll back the update on failure, but keep a record of signature failures.
+			let result = with_transaction(|| {
+				match Self::process_finalized_header(finalized_header_update) {
+					Ok(verified_twice) => TransactionOutcome::Commit(Ok(verified_twice)),
+					Err(err) => TransactionOutcome::Rollback(Err(err)),
+				}
+			});
+
+			let verified_twice = match result {
+				Ok(verified_twice) => verified_twice,
+				Err(err) => {
+					log::error!(
+						target: "ethereum-beacon-client",
+						"Finalized header update failed with error {:?}",
+						err
+					);
+					if err == Error::<T>::FinalizedHeaderConflict.into() {
+						Self::deposit_event(Event::FinalizedHeaderConflict { slot });
+					}
+					Self::deposit_event(Event::FinalizedHeaderImportFailed {
+						slot,
+						retryable: Self::is_retryable(&err),
+					});
+					if Self::is_signature_failure(&err) {
+						Self::record_failed_verification(slot, relayer, err);
+					}
+					return Err(err.into());
+				},
+			};
+
+			// Only headers advancing the light client are rewarded, so that relaying old
+			// headers can't drain the reward pool.
+			if previous_latest_slot.map_or(true, |latest| slot > latest) {
+				T::RelayerRewards::reward(&relayer);
 			}
 
 			log::trace!(
@@ -301,25 +811,152 @@ pub mod pallet {
 				slot
 			);
 
+			Ok(Self::signature_verification_weight(verified_twice).into())
+		}
+
+		/// Imports the latest header signed by the sync committee without waiting for it to be
+		/// finalized. The header is only stored as the optimistic head, never as a finalized
+		/// header, so it offers lower latency at the cost of weaker guarantees.
+		#[pallet::weight(SIGNATURE_VERIFICATION_WEIGHT.saturating_mul(2))]
+		#[transactional]
+		pub fn optimistic_header_update(
+			origin: OriginFor<T>,
+			attested_header: BeaconBlockHeader,
+			sync_aggregate: SyncAggregate,
+			fork_version: ForkVersion,
+		) -> DispatchResultWithPostInfo {
+			let _sender = T::HeaderSubmissionOrigin::ensure_origin(origin)?;
+
+			log::trace!(
+				target: "ethereum-beacon-client",
+				"💫 Received optimistic header update for slot {}.",
+				attested_header.slot
+			);
+
+			let verified_twice =
+				Self::process_optimistic_header(attested_header, sync_aggregate, fork_version)?;
+
+			Ok(Self::signature_verification_weight(verified_twice).into())
+		}
+
+		/// Overwrites the genesis validators root recorded during initial sync. Intended only
+		/// for recovering from a wrong root being recorded, not for regular relaying.
+		#[pallet::weight(1_000_000)]
+		#[transactional]
+		pub fn set_genesis_validators_root(
+			origin: OriginFor<T>,
+			validators_root: H256,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			log::info!(
+				target: "ethereum-beacon-client",
+				"💫 Updating genesis validators root to {:?}.",
+				validators_root
+			);
+
+			Self::store_genesis(Genesis { validators_root });
+			Self::deposit_event(Event::GenesisRootUpdated(validators_root));
+
+			Ok(())
+		}
+
+		/// Stores `sync_committee` for `period` without verifying it against a finalized
+		/// header. Intended only for test networks, to move the light client to a chosen
+		/// committee without assembling a valid period update.
+		#[pallet::weight(1_000_000)]
+		#[transactional]
+		pub fn force_store_sync_committee(
+			origin: OriginFor<T>,
+			period: u64,
+			sync_committee: SyncCommittee,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::validate_sync_committee(&sync_committee)?;
+
+			log::warn!(
+				target: "ethereum-beacon-client",
+				"💫 Forcing sync committee for period {}.",
+				period
+			);
+
+			Self::store_sync_committee(period, sync_committee);
+			Self::deposit_event(Event::ForcedCommitteeStored(period));
+
+			Ok(())
+		}
+
+		/// Restores light client state from a snapshot taken with [`Pallet::export_state`],
+		/// trusting it without verification. Used to bring up the light client from a known
+		/// good checkpoint rather than replaying its history.
+		#[pallet::weight(1_000_000)]
+		#[transactional]
+		pub fn import_state(origin: OriginFor<T>, snapshot: LightClientSnapshot) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::validate_sync_committee(&snapshot.current_sync_committee)?;
+			if let Some(next_sync_committee) = &snapshot.next_sync_committee {
+				Self::validate_sync_committee(next_sync_committee)?;
+			}
+
+			let header = snapshot.latest_finalized_header;
+			let slot = header.slot;
+
+			log::warn!(
+				target: "ethereum-beacon-client",
+				"💫 Importing light client state at slot {}.",
+				slot
+			);
+
+			// The snapshot replaces the light client's position outright, even if it is older
+			// than the stored state, so that `export_state` returns it unchanged. Per-period
+			// header counts only track imported headers and are left alone.
+			let period = slot.sync_period();
+			<SyncCommittees<T>>::insert(period, snapshot.current_sync_committee);
+			match snapshot.next_sync_committee {
+				Some(next_sync_committee) => {
+					<SyncCommittees<T>>::insert(period + 1, next_sync_committee);
+					<HighestStoredPeriod<T>>::put(period + 1);
+				},
+				None => {
+					<SyncCommittees<T>>::remove(period + 1);
+					<HighestStoredPeriod<T>>::put(period);
+				},
+			}
+
+			let block_root: H256 = merklization::hash_tree_root_beacon_header(header.clone())
+				.map_err(|_| Error::<T>::InvalidHash)?
+				.into();
+			<FinalizedHeaders<T>>::insert(block_root, header);
+			<FinalizedHeadersBySlot<T>>::insert(slot, block_root);
+			<LatestFinalizedSlot<T>>::put(slot);
+			<LatestFinalizedImportTime<T>>::put(T::TimeProvider::now().as_secs());
+			<FallingBehindReported<T>>::kill();
+
+			Self::store_genesis(Genesis { validators_root: snapshot.genesis_validators_root });
+			Self::deposit_event(Event::SnapshotImported(slot));
+
 			Ok(())
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
 		fn process_initial_sync(initial_sync: InitialSync) -> DispatchResult {
+			Self::validate_proposer_index(&initial_sync.header)?;
+
+			let gindex = Self::generalized_indices(initial_sync.header.slot).current_sync_committee;
 			Self::verify_sync_committee(
 				initial_sync.current_sync_committee.clone(),
 				initial_sync.current_sync_committee_branch,
 				initial_sync.header.state_root,
-				CURRENT_SYNC_COMMITTEE_DEPTH,
-				CURRENT_SYNC_COMMITTEE_INDEX,
+				gindex.depth,
+				gindex.index,
 			)?;
 
 			let period = Self::compute_current_sync_period(initial_sync.header.slot);
 			Self::store_sync_committee(period, initial_sync.current_sync_committee);
 
 			let block_root: H256 = merklization::hash_tree_root_beacon_header(initial_sync.header.clone())
-				.map_err(|_| DispatchError::Other("Header hash tree root failed"))?.into();
+				.map_err(|_| Error::<T>::InvalidHash)?.into();
 			Self::store_header(block_root, initial_sync.header);
 
 			Self::store_genesis(Genesis { validators_root: initial_sync.validators_root });
@@ -330,29 +967,36 @@ pub mod pallet {
 		fn process_sync_committee_period_update(
 			update: SyncCommitteePeriodUpdate,
 		) -> DispatchResult {
-			let sync_committee_bits = Self::convert_to_binary(update.sync_aggregate.sync_committee_bits.clone());
+			Self::validate_proposer_index(&update.attested_header)?;
+			Self::validate_proposer_index(&update.finalized_header)?;
+			Self::validate_sync_aggregate(&update.sync_aggregate)?;
+
+			let sync_committee_bits = Self::expand_sync_committee_bits(&update.sync_aggregate.sync_committee_bits)?;
 			Self::sync_committee_participation_is_supermajority(sync_committee_bits.clone())?;
-			Self::verify_sync_committee(
-				update.next_sync_committee.clone(),
-				update.next_sync_committee_branch,
-				update.finalized_header.state_root,
-				NEXT_SYNC_COMMITTEE_DEPTH,
-				NEXT_SYNC_COMMITTEE_INDEX,
-			)?;
 
+			// The finalized header's state root only becomes trustworthy once the header is
+			// proven against the attested header, so check that before the committee proof.
 			let block_root: H256 = merklization::hash_tree_root_beacon_header(update.finalized_header.clone())
-				.map_err(|_| DispatchError::Other("Header hash tree root failed"))?.into();
+				.map_err(|_| Error::<T>::InvalidHash)?.into();
+			let gindex = Self::generalized_indices(update.attested_header.slot).finalized_root;
 			Self::verify_header(
 				block_root,
 				update.finality_branch,
 				update.attested_header.state_root,
-				FINALIZED_ROOT_DEPTH,
-				FINALIZED_ROOT_INDEX,
+				gindex.depth,
+				gindex.index,
 			)?;
 
-			let current_period = Self::compute_current_sync_period(update.attested_header.slot);
-			Self::store_sync_committee(current_period + 1, update.next_sync_committee);
+			let gindex = Self::generalized_indices(update.finalized_header.slot).next_sync_committee;
+			Self::verify_sync_committee(
+				update.next_sync_committee.clone(),
+				update.next_sync_committee_branch,
+				update.finalized_header.state_root,
+				gindex.depth,
+				gindex.index,
+			)?;
 
+			let current_period = Self::compute_current_sync_period(update.attested_header.slot);
 			let current_sync_committee = <SyncCommittees<T>>::get(current_period);
 			let genesis = <ChainGenesis<T>>::get();
 			Self::verify_signed_header(
@@ -364,41 +1008,251 @@ pub mod pallet {
 				genesis.validators_root,
 			)?;
 
+			Self::store_sync_committee(current_period + 1, update.next_sync_committee);
 			Self::store_header(block_root, update.finalized_header);
 
 			Ok(())
 		}
 
-		fn process_finalized_header(update: FinalizedHeaderUpdate) -> DispatchResult {
-			let sync_committee_bits = Self::convert_to_binary(update.sync_aggregate.sync_committee_bits.clone());
+		/// Returns whether the sync committee signature had to be verified twice.
+		fn process_finalized_header(update: FinalizedHeaderUpdate) -> Result<bool, DispatchError> {
+			let block_root: H256 = merklization::hash_tree_root_beacon_header(update.finalized_header.clone())
+				.map_err(|_| Error::<T>::InvalidHash)?.into();
+			let stored_root = <FinalizedHeadersBySlot<T>>::get(update.finalized_header.slot);
+			ensure!(stored_root != Some(block_root), Error::<T>::AlreadyImported);
+			// A header with zeroed roots can still be proven, but can't anchor any later proofs.
+			ensure!(
+				!update.finalized_header.state_root.is_zero() &&
+					!update.finalized_header.body_root.is_zero(),
+				Error::<T>::DegenerateHeader
+			);
+			ensure!(
+				!T::RequireEpochAlignedFinalizedSlot::get() ||
+					update.finalized_header.slot.0 % SLOTS_PER_EPOCH == 0,
+				Error::<T>::UnalignedFinalizedSlot
+			);
+			// Finality always trails the head, so a finalized header can't be at or after the
+			// attested header proving it.
+			ensure!(
+				update.finalized_header.slot < update.attested_header.slot,
+				Error::<T>::FinalizedHeaderNotBeforeAttested
+			);
+			let expected_slot = Self::expected_current_slot(T::TimeProvider::now().as_secs());
+			ensure!(
+				update.finalized_header.slot.0 <= expected_slot.saturating_add(T::MaxFutureSlots::get()),
+				Error::<T>::HeaderFromFuture
+			);
+			Self::validate_proposer_index(&update.attested_header)?;
+			Self::validate_proposer_index(&update.finalized_header)?;
+			Self::validate_sync_aggregate(&update.sync_aggregate)?;
+
+			let sync_committee_bits = Self::expand_sync_committee_bits(&update.sync_aggregate.sync_committee_bits)?;
 			Self::sync_committee_participation_is_supermajority(sync_committee_bits.clone())?;
 
-			let block_root: H256 = merklization::hash_tree_root_beacon_header(update.finalized_header.clone())
-				.map_err(|_| DispatchError::Other("Header hash tree root failed"))?.into();
+			let gindex = Self::generalized_indices(update.attested_header.slot).finalized_root;
 			Self::verify_header(
 				block_root,
 				update.finality_branch,
 				update.attested_header.state_root,
-				FINALIZED_ROOT_DEPTH,
-				FINALIZED_ROOT_INDEX,
+				gindex.depth,
+				gindex.index,
 			)?;
 
 			let current_period = Self::compute_current_sync_period(update.attested_header.slot);
+			let reuse_verified = T::ReuseVerifiedSigningRoots::get() &&
+				<FinalizedHeaders<T>>::contains_key(update.finalized_header.parent_root) &&
+				<VerifiedSigningRoots<T>>::get().contains(&Self::sync_committee_signing_root(
+					update.attested_header.clone(),
+					update.fork_version,
+				)?);
+			let verified_twice = if reuse_verified {
+				false
+			} else {
+				Self::verify_sync_aggregate(
+					current_period,
+					sync_committee_bits.clone(),
+					update.sync_aggregate.sync_committee_signature,
+					update.fork_version,
+					update.attested_header,
+				)?
+			};
+
+			// Two verified headers at the same slot point to a finality equivocation or a
+			// relayer bug. Keep the stored header and reject the update.
+			ensure!(stored_root.is_none(), Error::<T>::FinalizedHeaderConflict);
+
+			Self::record_participation(
+				current_period,
+				Self::get_sync_committee_sum(sync_committee_bits)?,
+			);
+			Self::store_header(block_root, update.finalized_header);
+
+			Ok(verified_twice)
+		}
+
+		/// Returns whether the sync committee signature had to be verified twice.
+		fn process_optimistic_header(
+			attested_header: BeaconBlockHeader,
+			sync_aggregate: SyncAggregate,
+			fork_version: ForkVersion,
+		) -> Result<bool, DispatchError> {
+			if let Some(optimistic_header) = <OptimisticHeader<T>>::get() {
+				ensure!(attested_header.slot > optimistic_header.slot, Error::<T>::StaleOptimisticHeader);
+			}
+			Self::validate_proposer_index(&attested_header)?;
+			Self::validate_sync_aggregate(&sync_aggregate)?;
+
+			let sync_committee_bits = Self::expand_sync_committee_bits(&sync_aggregate.sync_committee_bits)?;
+			Self::sync_committee_participation_is_supermajority(sync_committee_bits.clone())?;
+
+			let verified_twice = Self::verify_sync_aggregate(
+				Self::compute_current_sync_period(attested_header.slot),
+				sync_committee_bits,
+				sync_aggregate.sync_committee_signature,
+				fork_version,
+				attested_header.clone(),
+			)?;
+
+			<OptimisticHeader<T>>::put(attested_header);
+
+			Ok(verified_twice)
+		}
+
+		/// Verifies `attested_header` was signed by the sync committee of `current_period`, or,
+		/// near the end of the period, of the following period if it is known. Returns whether
+		/// the signature was verified a second time against the following period's committee.
+		fn verify_sync_aggregate(
+			current_period: u64,
+			sync_committee_bits: Vec<u8>,
+			sync_committee_signature: Vec<u8>,
+			fork_version: ForkVersion,
+			attested_header: BeaconBlockHeader,
+		) -> Result<bool, DispatchError> {
 			let sync_committee = <SyncCommittees<T>>::get(current_period);
 			if (SyncCommittee { pubkeys: vec![], aggregate_pubkey: PublicKey([0; 48]) }) == sync_committee {
 				return Err(Error::<T>::SyncCommitteeMissing.into());
 			}
 			let genesis = <ChainGenesis<T>>::get();
-			Self::verify_signed_header(
-				sync_committee_bits,
-				update.sync_aggregate.sync_committee_signature,
+			let verified = Self::verify_signed_header(
+				sync_committee_bits.clone(),
+				sync_committee_signature.clone(),
 				sync_committee.pubkeys,
-				update.fork_version,
-				update.attested_header,
+				fork_version,
+				attested_header.clone(),
 				genesis.validators_root,
-			)?;
+			);
 
-			Self::store_header(block_root, update.finalized_header);
+			if let Err(err) = verified {
+				// Near a period boundary the header may have been signed by the committee of the
+				// following period, so fall back to it if we already know it.
+				if !Self::is_near_period_end(attested_header.slot, current_period) {
+					return Err(err);
+				}
+				let next_period = period_context(period_end_slot(current_period)).adjacent_period;
+				let next_sync_committee = <SyncCommittees<T>>::get(next_period);
+				if (SyncCommittee { pubkeys: vec![], aggregate_pubkey: PublicKey([0; 48]) }) == next_sync_committee {
+					return Err(err);
+				}
+
+				log::trace!(
+					target: "ethereum-beacon-client",
+					"💫 Signature verification failed for period {}, retrying with next sync committee.",
+					current_period
+				);
+
+				Self::verify_signed_header(
+					sync_committee_bits,
+					sync_committee_signature,
+					next_sync_committee.pubkeys,
+					fork_version,
+					attested_header,
+					genesis.validators_root,
+				)?;
+
+				return Ok(true);
+			}
+
+			Ok(false)
+		}
+
+		/// Whether `slot` lies within `PeriodBoundarySlots` of the end of `period`.
+		pub(super) fn is_near_period_end(slot: Slot, period: u64) -> bool {
+			slot.0.saturating_add(T::PeriodBoundarySlots::get()) > period_end_slot(period).0
+		}
+
+		/// Actual weight of a header import, which only includes the second signature
+		/// verification if it was needed.
+		fn signature_verification_weight(verified_twice: bool) -> Option<Weight> {
+			if verified_twice {
+				None
+			} else {
+				Some(SIGNATURE_VERIFICATION_WEIGHT)
+			}
+		}
+
+		fn is_signature_failure(err: &DispatchError) -> bool {
+			*err == Error::<T>::InvalidSignature.into() ||
+				*err == Error::<T>::InvalidSignaturePoint.into() ||
+				*err == Error::<T>::InvalidAggregatePublicKeys.into() ||
+				*err == Error::<T>::SignatureVerificationFailed.into()
+		}
+
+		/// Whether an update rejected with `err` may be accepted if resubmitted unchanged, because
+		/// the failure depends on light client state or time rather than on the update itself.
+		fn is_retryable(err: &DispatchError) -> bool {
+			*err == Error::<T>::SyncCommitteeMissing.into() ||
+				*err == Error::<T>::HeaderFromFuture.into()
+		}
+
+		fn record_failed_verification(slot: Slot, relayer: T::AccountId, reason: DispatchError) {
+			<FailedVerifications<T>>::mutate(|failures| {
+				if failures.len() >= T::MaxFailedVerifications::get() as usize {
+					failures.remove(0);
+				}
+				failures.push(FailedVerification { slot, relayer, reason });
+			});
+		}
+
+		pub(super) fn record_participation(period: u64, participants: u64) {
+			<ParticipationStatsByPeriod<T>>::mutate(period, |stats| {
+				stats.headers = stats.headers.saturating_add(1);
+				stats.participants = stats.participants.saturating_add(participants);
+			});
+		}
+
+		/// Average share of the sync committee that signed the finalized headers imported for
+		/// `period`, or `None` if no header has been imported for it.
+		pub fn participation_rate(period: u64) -> Option<Perbill> {
+			let stats = <ParticipationStatsByPeriod<T>>::get(period);
+			if stats.headers == 0 {
+				return None;
+			}
+
+			Some(Perbill::from_rational(
+				stats.participants,
+				stats.headers.saturating_mul(SYNC_COMMITTEE_SIZE as u64),
+			))
+		}
+
+		pub(super) fn validate_proposer_index(header: &BeaconBlockHeader) -> DispatchResult {
+			ensure!(
+				header.proposer_index <= T::MaxProposerIndex::get(),
+				Error::<T>::InvalidProposerIndex
+			);
+
+			Ok(())
+		}
+
+		/// Checks the sync aggregate is well-formed before doing any expensive work with it.
+		pub(super) fn validate_sync_aggregate(sync_aggregate: &SyncAggregate) -> DispatchResult {
+			ensure!(
+				sync_aggregate.sync_committee_bits.0.len() == SYNC_COMMITTEE_SIZE / 8,
+				Error::<T>::InvalidSyncCommitteeBitsLength
+			);
+			ensure!(
+				sync_aggregate.sync_committee_signature.len() == SIGNATURE_SIZE,
+				Error::<T>::InvalidSyncAggregate
+			);
 
 			Ok(())
 		}
@@ -413,12 +1267,16 @@ pub mod pallet {
 		) -> DispatchResult {
 			let mut participant_pubkeys: Vec<PublicKey> = Vec::new();
 			// Gathers all the pubkeys of the sync committee members that participated in siging the header.
+			// BLS aggregation is commutative, so the order they are gathered in doesn't matter.
 			for (bit, pubkey) in sync_committee_bits.iter().zip(sync_committee_pubkeys.iter()) {
 				if *bit == 1 as u8 {
 					let pubk = pubkey.clone();
 					participant_pubkeys.push(pubk);
 				}
 			}
+			// Callers check participation beforehand, but an empty set must never reach
+			// aggregation whatever the bits claim.
+			ensure!(!participant_pubkeys.is_empty(), Error::<T>::InsufficientSyncCommitteeParticipants);
 
 			let domain_type = DOMAIN_SYNC_COMMITTEE.to_vec();
 			// Domains are used for for seeds, for signatures, and for selecting aggregators.
@@ -426,6 +1284,15 @@ pub mod pallet {
 			// Hash tree root of SigningData - object root + domain
 			let signing_root = Self::compute_signing_root(header, domain)?;
 
+			// Relayers racing to submit updates for the same attested header carry identical
+			// signatures, so skip the BLS check if these exact inputs were verified recently.
+			let cache_key: H256 =
+				blake2_256(&(signing_root, &sync_committee_signature, &participant_pubkeys).encode())
+					.into();
+			if <VerifiedSignatures<T>>::get().contains(&cache_key) {
+				return Ok(());
+			}
+
 			// Verify sync committee aggregate signature.
 			Self::bls_fast_aggregate_verify(
 				participant_pubkeys,
@@ -433,44 +1300,63 @@ pub mod pallet {
 				sync_committee_signature,
 			)?;
 
+			<VerifiedSignatures<T>>::mutate(|keys| {
+				if keys.len() >= VERIFIED_SIGNATURE_CACHE_SIZE {
+					keys.remove(0);
+				}
+				keys.push(cache_key);
+			});
+
+			if T::ReuseVerifiedSigningRoots::get() {
+				<VerifiedSigningRoots<T>>::mutate(|roots| {
+					if roots.len() >= VERIFIED_SIGNATURE_CACHE_SIZE {
+						roots.remove(0);
+					}
+					roots.push(signing_root);
+				});
+			}
+
 			Ok(())
 		}
 
+		/// Signing root a sync committee signs for `header` under `fork_version`.
+		pub(super) fn sync_committee_signing_root(
+			header: BeaconBlockHeader,
+			fork_version: ForkVersion,
+		) -> Result<Root, DispatchError> {
+			let validators_root = <ChainGenesis<T>>::get().validators_root;
+			let domain =
+				Self::compute_domain(DOMAIN_SYNC_COMMITTEE.to_vec(), Some(fork_version), validators_root)?;
+			Self::compute_signing_root(header, domain)
+		}
+
 		pub(super) fn bls_fast_aggregate_verify(
 			pubkeys: Vec<PublicKey>,
 			message: H256,
 			signature: Vec<u8>,
 		) -> DispatchResult {
-			let sig = Signature::from_bytes(&signature[..]);
-			if let Err(_e) = sig {
-				return Err(Error::<T>::InvalidSignature.into());
-			}
-			
-			let agg_sig = AggregateSignature::from_signature(&sig.unwrap());
-
-			let public_keys_res: Result<Vec<milagro_bls::PublicKey>, _> =
-				pubkeys.iter().map(|bytes| milagro_bls::PublicKey::from_bytes_unchecked(&bytes.0)).collect();
-			if let Err(e) = public_keys_res {
-				match e {
-					AmclError::InvalidPoint => return Err(Error::<T>::InvalidSignaturePoint.into()),
-					_ => return Err(Error::<T>::InvalidSignature.into()),
-				};
-			}
-
-			let agg_pub_key_res = AggregatePublicKey::into_aggregate(&public_keys_res.unwrap());
-			if let Err(_e) = agg_pub_key_res {
-				return Err(Error::<T>::InvalidAggregatePublicKeys.into());
-			}
+			let result = metrics::timed("BLS fast aggregate verification", || {
+				Self::verify_aggregate_signature(pubkeys, message, signature)
+			});
+			metrics::record_verification(result.is_ok());
 
-			ensure!(
-				agg_sig.fast_aggregate_verify_pre_aggregated(
-					&message.as_bytes(),
-					&agg_pub_key_res.unwrap()
-				),
-				Error::<T>::SignatureVerificationFailed
-			);
+			result
+		}
 
-			Ok(())
+		fn verify_aggregate_signature(
+			pubkeys: Vec<PublicKey>,
+			message: H256,
+			signature: Vec<u8>,
+		) -> DispatchResult {
+			T::BlsVerifier::fast_aggregate_verify(&pubkeys, message, &signature).map_err(|err| {
+				match err {
+					BlsError::InvalidSignature => Error::<T>::InvalidSignature,
+					BlsError::InvalidSignaturePoint => Error::<T>::InvalidSignaturePoint,
+					BlsError::InvalidAggregatePublicKeys => Error::<T>::InvalidAggregatePublicKeys,
+					BlsError::SignatureVerificationFailed => Error::<T>::SignatureVerificationFailed,
+				}
+				.into()
+			})
 		}
 
 		pub(super) fn compute_signing_root(
@@ -478,13 +1364,13 @@ pub mod pallet {
 			domain: Domain,
 		) -> Result<Root, DispatchError> {
 			let beacon_header_root = merklization::hash_tree_root_beacon_header(beacon_header)
-				.map_err(|_| DispatchError::Other("Beacon header hash tree root failed"))?;
+				.map_err(|_| Error::<T>::InvalidHash)?;
 
 			let hash_root = merklization::hash_tree_root_signing_data(SigningData {
 				object_root: beacon_header_root.into(),
 				domain,
 			})
-			.map_err(|_| DispatchError::Other("Signing root hash tree root failed"))?;
+			.map_err(|_| Error::<T>::InvalidHash)?;
 
 			Ok(hash_root.into())
 		}
@@ -496,13 +1382,14 @@ pub mod pallet {
 			depth: u64,
 			index: u64,
 		) -> DispatchResult {
-			let sync_committee_root =
-				merklization::hash_tree_root_sync_committee(sync_committee)
-					.map_err(|_| DispatchError::Other("Sync committee hash tree root failed"))?;
+			Self::validate_sync_committee(&sync_committee)?;
+			Self::ensure_well_formed_branch(&sync_committee_branch)?;
+
+			let sync_committee_root = Self::sync_committee_root(&sync_committee)?;
 
 			ensure!(
 				Self::is_valid_merkle_branch(
-					sync_committee_root.into(),
+					sync_committee_root,
 					sync_committee_branch,
 					depth,
 					index,
@@ -514,13 +1401,37 @@ pub mod pallet {
 			Ok(())
 		}
 
-		fn verify_header(
+		/// Checks a sync committee Merkle proof against `state_root` without touching storage,
+		/// so relayers can validate a proof before submitting it.
+		pub fn check_sync_committee_proof(
+			committee: &SyncCommittee,
+			branch: &ProofBranch,
+			state_root: H256,
+			depth: u64,
+			index: u64,
+		) -> bool {
+			Self::verify_sync_committee(committee.clone(), branch.clone(), state_root, depth, index)
+				.is_ok()
+		}
+
+		/// Hash tree root of `sync_committee`, as proven in a beacon state. Lets operators check a
+		/// committee they hold against the leaf of a Merkle proof without submitting an update.
+		pub fn sync_committee_root(sync_committee: &SyncCommittee) -> Result<H256, DispatchError> {
+			let root = merklization::hash_tree_root_sync_committee(sync_committee.clone())
+				.map_err(|_| Error::<T>::InvalidHash)?;
+
+			Ok(root.into())
+		}
+
+		pub(super) fn verify_header(
 			block_root: H256,
 			proof_branch: ProofBranch,
 			attested_header_state_root: H256,
 			depth: u64,
 			index: u64,
 		) -> DispatchResult {
+			Self::ensure_well_formed_branch(&proof_branch)?;
+
 			ensure!(
 				Self::is_valid_merkle_branch(
 					block_root,
@@ -535,32 +1446,322 @@ pub mod pallet {
 			Ok(())
 		}
 
-		fn store_sync_committee(period: u64, sync_committee: SyncCommittee) {
+		pub(super) fn validate_sync_committee(sync_committee: &SyncCommittee) -> DispatchResult {
+			ensure!(
+				sync_committee.pubkeys.len() == SYNC_COMMITTEE_SIZE,
+				Error::<T>::InvalidSyncCommitteeSize
+			);
+			// Neither the default nor the point at infinity can be a real committee's aggregate.
+			ensure!(
+				sync_committee.aggregate_pubkey.0 != [0u8; 48] &&
+					sync_committee.aggregate_pubkey.0 != G1_POINT_AT_INFINITY,
+				Error::<T>::InvalidAggregatePublicKeys
+			);
+
+			Ok(())
+		}
+
+		/// A branch made up entirely of zero hashes points at a relayer building the proof
+		/// incorrectly, rather than at a proof that doesn't match, so report it separately.
+		fn ensure_well_formed_branch(branch: &ProofBranch) -> DispatchResult {
+			ensure!(
+				branch.len() <= T::MaxProofBranchLength::get() as usize,
+				Error::<T>::ProofBranchTooLong
+			);
+			ensure!(
+				branch.is_empty() || branch.iter().any(|node| !node.is_zero()),
+				Error::<T>::MalformedProofBranch
+			);
+
+			Ok(())
+		}
+
+		/// Verifies that `message` was emitted in the execution block of the finalized beacon
+		/// block at `beacon_proof.slot`, returning the message's log.
+		pub fn verify_execution_proof(
+			message: &Message,
+			beacon_proof: &BeaconProof,
+		) -> Result<Log, DispatchError> {
+			let block_root = <FinalizedHeadersBySlot<T>>::get(Slot(beacon_proof.slot))
+				.ok_or(Error::<T>::MissingHeader)?;
+			let beacon_header =
+				<FinalizedHeaders<T>>::get(block_root).ok_or(Error::<T>::MissingHeader)?;
+
+			Self::ensure_well_formed_branch(&beacon_proof.execution_branch)?;
+
+			let execution_block_hash = beacon_proof.execution_header.compute_hash();
+			ensure!(execution_block_hash == message.proof.block_hash, Error::<T>::InvalidProof);
+			let gindex = Self::execution_block_hash_index(beacon_header.slot);
+			ensure!(
+				Self::is_valid_merkle_branch(
+					execution_block_hash,
+					beacon_proof.execution_branch.clone(),
+					gindex.depth,
+					gindex.index,
+					beacon_header.body_root
+				),
+				Error::<T>::InvalidProof
+			);
+
+			let receipt = beacon_proof
+				.execution_header
+				.check_receipt_proof(&message.proof.data.1)
+				.ok_or(Error::<T>::InvalidProof)?
+				.map_err(|_| Error::<T>::InvalidProof)?;
+
+			let log: Log = rlp::decode(&message.data).map_err(|_| Error::<T>::DecodeFailed)?;
+			ensure!(receipt.contains_log(&log), Error::<T>::InvalidProof);
+
+			Ok(log)
+		}
+
+		pub fn chain_config() -> ChainConfig {
+			ChainConfig {
+				genesis_validators_root: <ChainGenesis<T>>::get().validators_root,
+				slots_per_epoch: SLOTS_PER_EPOCH,
+				epochs_per_period: EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
+				electra_fork_epoch: T::ElectraForkEpoch::get(),
+				deneb_fork_epoch: T::DenebForkEpoch::get(),
+				fork_versions: ForkVersions {
+					genesis: T::GenesisForkVersion::get(),
+					deneb: T::DenebForkVersion::get(),
+					electra: T::ElectraForkVersion::get(),
+				},
+			}
+		}
+
+		/// The sync committee for the period after the current one, once a period update has
+		/// installed it.
+		pub fn next_sync_committee() -> Option<SyncCommittee> {
+			let period = Self::current_period()?;
+			<SyncCommittees<T>>::try_get(period + 1).ok()
+		}
+
+		/// The state needed to resume the light client elsewhere, or `None` before initial sync.
+		pub fn export_state() -> Option<LightClientSnapshot> {
+			let slot = <LatestFinalizedSlot<T>>::get()?;
+			let latest_finalized_header = Self::verified_header(slot)?;
+			let period = slot.sync_period();
+
+			Some(LightClientSnapshot {
+				genesis_validators_root: <ChainGenesis<T>>::get().validators_root,
+				latest_finalized_header,
+				current_sync_committee: <SyncCommittees<T>>::get(period),
+				next_sync_committee: <SyncCommittees<T>>::try_get(period + 1).ok(),
+			})
+		}
+
+		/// The sync committee period of the latest finalized header, or `None` before initial
+		/// sync.
+		pub fn current_period() -> Option<u64> {
+			<LatestFinalizedSlot<T>>::get().map(|slot| slot.sync_period())
+		}
+
+		/// Number of finalized headers stored for sync committee `period`, to spot gaps in
+		/// relayer coverage.
+		pub fn finalized_count_in_period(period: u64) -> u32 {
+			<FinalizedHeaderCountByPeriod<T>>::get(period)
+		}
+
+		/// The sync committee period a relayer should submit the next period update for.
+		pub fn next_expected_period() -> u64 {
+			Self::highest_stored_period().saturating_add(1)
+		}
+
+		/// Returns the finalized header at `slot`, if one has been verified and imported.
+		/// Headers are only written to storage once their finality proof and sync committee
+		/// signature have been verified, so anything returned here can be trusted.
+		pub fn verified_header(slot: Slot) -> Option<BeaconBlockHeader> {
+			<FinalizedHeadersBySlot<T>>::get(slot).and_then(<FinalizedHeaders<T>>::get)
+		}
+
+		/// Whether the header with hash tree root `block_root` has been verified and imported as
+		/// finalized, so that other pallets can trust data proven against it.
+		pub fn is_finalized(block_root: H256) -> bool {
+			<FinalizedHeaders<T>>::contains_key(block_root)
+		}
+
+		pub fn header_status(slot: Slot) -> HeaderStatus {
+			if <FinalizedHeadersBySlot<T>>::contains_key(slot) {
+				HeaderStatus::Finalized
+			} else {
+				HeaderStatus::Unknown
+			}
+		}
+
+		/// Returns the sync committee for the period `slot` falls in, if it has been stored.
+		pub fn sync_committee_for_slot(slot: Slot) -> Option<SyncCommittee> {
+			let period = Self::compute_current_sync_period(slot);
+			<SyncCommittees<T>>::try_get(period).ok()
+		}
+
+		pub(super) fn store_sync_committee(period: u64, sync_committee: SyncCommittee) {
+			let duplicates = Self::count_duplicate_pubkeys(&sync_committee.pubkeys);
+			if duplicates > 0 {
+				log::warn!(
+					target: "ethereum-beacon-client",
+					"💫 Sync committee for period {} has {} duplicate public keys.",
+					period,
+					duplicates
+				);
+				Self::deposit_event(Event::SuspiciousCommittee { period, duplicates });
+			}
+
 			<SyncCommittees<T>>::insert(period, sync_committee);
+
+			<HighestStoredPeriod<T>>::mutate(|highest| {
+				if period > *highest {
+					*highest = period;
+				}
+			});
 		}
 
-		fn store_header(block_root: H256, header: BeaconBlockHeader) {
+		/// Number of entries in `pubkeys` which repeat an earlier entry.
+		pub(super) fn count_duplicate_pubkeys(pubkeys: &[PublicKey]) -> u32 {
+			let mut sorted: Vec<&[u8; 48]> = pubkeys.iter().map(|pubkey| &pubkey.0).collect();
+			sorted.sort_unstable();
+			sorted.windows(2).filter(|pair| pair[0] == pair[1]).count() as u32
+		}
+
+		pub(super) fn store_header(block_root: H256, header: BeaconBlockHeader) {
+			if T::VerifyParentLinkage::get() && Self::breaks_slot_ordering(&header) {
+				log::warn!(
+					target: "ethereum-beacon-client",
+					"💫 Finalized header at slot {} is inconsistent with its stored parent.",
+					header.slot
+				);
+				Self::deposit_event(Event::HeaderChainBroken(header.slot, block_root));
+			}
+
 			<FinalizedHeaders<T>>::insert(block_root, header.clone());
 
 			<FinalizedHeadersBySlot<T>>::insert(header.slot, block_root);
+
+			<FinalizedHeaderCountByPeriod<T>>::mutate(header.slot.sync_period(), |count| {
+				*count = count.saturating_add(1)
+			});
+
+			<LatestFinalizedSlot<T>>::mutate(|latest| {
+				if latest.map_or(true, |latest| header.slot > latest) {
+					*latest = Some(header.slot);
+				}
+			});
+
+			<LatestFinalizedImportTime<T>>::put(T::TimeProvider::now().as_secs());
+			<FallingBehindReported<T>>::kill();
+		}
+
+		/// Estimated number of slots the light client is behind, based on the time since the
+		/// latest finalized header was imported. `None` before any header has been imported.
+		pub fn slots_behind_estimate() -> Option<u64> {
+			let imported_at = <LatestFinalizedImportTime<T>>::get()?;
+			let now = T::TimeProvider::now().as_secs();
+
+			Some(now.saturating_sub(imported_at).checked_div(T::SecondsPerSlot::get()).unwrap_or(0))
+		}
+
+		/// The beacon chain slot expected at unix time `now` (in seconds), derived from the
+		/// genesis time and slot duration. Zero before genesis.
+		pub fn expected_current_slot(now: u64) -> u64 {
+			now.saturating_sub(T::GenesisTime::get())
+				.checked_div(T::SecondsPerSlot::get())
+				.unwrap_or(0)
+		}
+
+		/// Number of slots between the latest finalized header and `current_slot`, or `None`
+		/// before initial sync. Zero if `current_slot` is not ahead of the latest header.
+		pub fn slots_since_last_finalized(current_slot: u64) -> Option<u64> {
+			let latest = <LatestFinalizedSlot<T>>::get()?;
+			Some(current_slot.saturating_sub(latest.into()))
+		}
+
+		/// Whether `header` names a stored parent that is not at a lower slot, or that skips
+		/// over the latest finalized header.
+		///
+		/// Finalized headers are usually an epoch or more apart, so a header whose parent is
+		/// not stored can't be checked without an ancestry proof and is not flagged.
+		fn breaks_slot_ordering(header: &BeaconBlockHeader) -> bool {
+			let parent = match <FinalizedHeaders<T>>::get(header.parent_root) {
+				Some(parent) => parent,
+				None => return false,
+			};
+			if parent.slot >= header.slot {
+				return true;
+			}
+			<LatestFinalizedSlot<T>>::get()
+				.map_or(false, |latest| latest > parent.slot && latest < header.slot)
 		}
 
 		fn store_genesis(genesis: Genesis) {
 			<ChainGenesis<T>>::put(genesis);
 		}
 
-		/// Sums the bit vector of sync committee particpation.
+		/// Sums the bit vector of sync committee particpation. The vector must be non-empty and
+		/// contain only `0` and `1` values.
 		///
 		/// # Examples
 		///
 		/// let sync_committee_bits = vec![0, 1, 0, 1, 1, 1];
-		/// ensure!(get_sync_committee_sum(sync_committee_bits), 4);
-		pub(super) fn get_sync_committee_sum(sync_committee_bits: Vec<u8>) -> u64 {
-			sync_committee_bits.iter().fold(0, |acc: u64, x| acc + *x as u64)
+		/// ensure!(get_sync_committee_sum(sync_committee_bits)?, 4);
+		pub(super) fn get_sync_committee_sum(sync_committee_bits: Vec<u8>) -> Result<u64, DispatchError> {
+			ensure!(!sync_committee_bits.is_empty(), Error::<T>::InvalidSyncCommitteeBits);
+
+			sync_committee_bits.iter().try_fold(0, |acc: u64, bit| match bit {
+				0 | 1 => Ok(acc + *bit as u64),
+				_ => Err(Error::<T>::InvalidSyncCommitteeBits.into()),
+			})
 		}
 
-		pub(super) fn compute_current_sync_period(slot: u64) -> u64 {
-			slot / SLOTS_PER_EPOCH / EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+		pub(super) fn compute_current_sync_period(slot: Slot) -> u64 {
+			slot.sync_period()
+		}
+
+		/// Returns the positions of the proven beacon state fields for the fork active at `slot`.
+		pub(super) fn generalized_indices(slot: Slot) -> GeneralizedIndices {
+			if slot.epoch() >= T::ElectraForkEpoch::get() {
+				GeneralizedIndices {
+					current_sync_committee: GeneralizedIndex {
+						index: CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA,
+						depth: CURRENT_SYNC_COMMITTEE_DEPTH_ELECTRA,
+					},
+					next_sync_committee: GeneralizedIndex {
+						index: NEXT_SYNC_COMMITTEE_INDEX_ELECTRA,
+						depth: NEXT_SYNC_COMMITTEE_DEPTH_ELECTRA,
+					},
+					finalized_root: GeneralizedIndex {
+						index: FINALIZED_ROOT_INDEX_ELECTRA,
+						depth: FINALIZED_ROOT_DEPTH_ELECTRA,
+					},
+				}
+			} else {
+				GeneralizedIndices {
+					current_sync_committee: GeneralizedIndex {
+						index: CURRENT_SYNC_COMMITTEE_INDEX,
+						depth: CURRENT_SYNC_COMMITTEE_DEPTH,
+					},
+					next_sync_committee: GeneralizedIndex {
+						index: NEXT_SYNC_COMMITTEE_INDEX,
+						depth: NEXT_SYNC_COMMITTEE_DEPTH,
+					},
+					finalized_root: GeneralizedIndex {
+						index: FINALIZED_ROOT_INDEX,
+						depth: FINALIZED_ROOT_DEPTH,
+					},
+				}
+			}
+		}
+
+		/// Returns the position of the execution block hash within the body of a beacon block
+		/// at `slot`, for the fork active at that slot.
+		pub(super) fn execution_block_hash_index(slot: Slot) -> GeneralizedIndex {
+			if slot.epoch() >= T::DenebForkEpoch::get() {
+				GeneralizedIndex {
+					index: EXECUTION_BLOCK_HASH_INDEX_DENEB,
+					depth: EXECUTION_BLOCK_HASH_DEPTH_DENEB,
+				}
+			} else {
+				GeneralizedIndex { index: EXECUTION_BLOCK_HASH_INDEX, depth: EXECUTION_BLOCK_HASH_DEPTH }
+			}
 		}
 
 		/// Return the domain for the domain_type and fork_version.
@@ -569,9 +1770,11 @@ pub mod pallet {
 			fork_version: Option<ForkVersion>,
 			genesis_validators_root: Root,
 		) -> Result<Domain, DispatchError> {
+			ensure!(domain_type.len() == 4, Error::<T>::InvalidDomainType);
+
 			let unwrapped_fork_version: ForkVersion;
 			if fork_version.is_none() {
-				unwrapped_fork_version = GENESIS_FORK_VERSION;
+				unwrapped_fork_version = T::GenesisForkVersion::get();
 			} else {
 				unwrapped_fork_version = fork_version.unwrap();
 			}
@@ -594,7 +1797,7 @@ pub mod pallet {
 				current_version,
 				genesis_validators_root: genesis_validators_root.into(),
 			})
-			.map_err(|_| DispatchError::Other("Fork data hash tree root failed"))?;
+			.map_err(|_| Error::<T>::InvalidHash)?;
 
 			Ok(hash_root.into())
 		}
@@ -623,7 +1826,7 @@ pub mod pallet {
 
 					return false;
 				}
-				if (index / (2u32.pow(i as u32) as u64) % 2) == 0 {
+				if index.checked_shr(i as u32).unwrap_or(0) % 2 == 0 {
 					// left node
 					let mut data = [0u8; 64];
 					data[0..32].copy_from_slice(&(value.0));
@@ -640,40 +1843,34 @@ pub mod pallet {
 			return value == root;
 		}
 
-		pub(super) fn convert_to_binary(input: Vec<u8>) -> Vec<u8> {
-			let mut result = Vec::new();
-
-			for input_decimal in input.iter() {
-				let mut tmp = Vec::new();
-				let mut remaining = *input_decimal;
-
-				while remaining > 0 {
-					let remainder = remaining % 2;
-					tmp.push(remainder);
-					remaining = remaining / 2;
-				}
-
-				// pad binary with 0s if length is less than 7
-				if tmp.len() < 8 {
-					for _i in tmp.len()..8 {
-						tmp.push(0)
-					}
-				}
-
-				result.append(&mut tmp);
-			}
-
-			result
+		/// Expands the sync committee bitfield into one entry per committee member, rejecting
+		/// a bitfield that doesn't cover exactly [`SYNC_COMMITTEE_SIZE`] members, since the
+		/// surplus bits would otherwise be dropped when paired with the committee's keys. Fails
+		/// with the same error as [`Self::validate_sync_aggregate`].
+		pub(super) fn expand_sync_committee_bits(
+			input: &SyncCommitteeBits,
+		) -> Result<Vec<u8>, DispatchError> {
+			let bits: Vec<u8> = input.iter_bits().collect();
+			ensure!(bits.len() == SYNC_COMMITTEE_SIZE, Error::<T>::InvalidSyncCommitteeBitsLength);
+
+			Ok(bits)
 		}
 
 		pub(super) fn sync_committee_participation_is_supermajority(sync_committee_bits: Vec<u8>) -> DispatchResult {
-			let sync_committee_sum = Self::get_sync_committee_sum(sync_committee_bits.clone());
+			let sync_committee_sum = Self::get_sync_committee_sum(sync_committee_bits.clone())?;
+			ensure!(sync_committee_sum > 0, Error::<T>::InsufficientSyncCommitteeParticipants);
 			ensure!(
-				(sync_committee_sum * 3 >= sync_committee_bits.clone().len() as u64 * 2),
+				(sync_committee_sum * 3 >= SYNC_COMMITTEE_SIZE as u64 * 2),
 				Error::<T>::SyncCommitteeParticipantsNotSupermajority
 			);
 
 			Ok(())
 		}
 	}
+
+	impl<T: Config> BeaconVerifier for Pallet<T> {
+		fn verify(message: &Message, beacon_proof: &BeaconProof) -> Result<Log, DispatchError> {
+			Self::verify_execution_proof(message, beacon_proof)
+		}
+	}
 }
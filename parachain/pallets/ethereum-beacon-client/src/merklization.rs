@@ -1,10 +1,9 @@
-use crate::{BeaconBlockHeader, SyncCommittee, ForkData, SigningData};
+use crate::{BeaconBlockHeader, SyncCommittee, ForkData, SigningData, PublicKey, SYNC_COMMITTEE_SIZE};
+use sp_io::hashing::sha2_256;
 
 use ssz_rs_derive::SimpleSerialize;
 use ssz_rs::{Deserialize, Sized, SimpleSerialize as SimpleSerializeTrait};
-use ssz_rs::prelude::Vector;
 use sp_std::convert::TryInto;
-use sp_std::iter::FromIterator;
 use sp_std::prelude::*;
 
 #[derive(Default, SimpleSerialize)]
@@ -16,12 +15,6 @@ pub struct SSZBeaconBlockHeader {
 	pub body_root: [u8; 32],
 }
 
-#[derive(Default, SimpleSerialize)]
-pub struct SSZSyncCommittee {
-	pub pubkeys: Vector<Vector<u8, 48>, 512>,
-	pub aggregate_pubkey: Vector<u8, 48>,
-}
-
 #[derive(Default, SimpleSerialize)]
 pub struct SSZForkData {
     pub current_version: [u8; 4],
@@ -34,16 +27,24 @@ pub struct SSZSigningData {
 	pub domain: [u8; 32],
 }
 
+#[cfg(test)]
+#[derive(Default, SimpleSerialize)]
+pub struct SSZGenesis {
+	pub validators_root: [u8; 32],
+}
+
 #[derive(Debug)]
 pub enum MerkleizationError {
     HashTreeRootError,
     HashTreeRootInvalidBytes,
-    InvalidLength
+    InvalidLength,
+    /// More items than the SSZ list limit, rejected before any hashing is done.
+    ListTooLong,
 }
 
 pub fn hash_tree_root_beacon_header(beacon_header: BeaconBlockHeader) -> Result<[u8; 32], MerkleizationError> {
     hash_tree_root(SSZBeaconBlockHeader{
-        slot: beacon_header.slot,
+        slot: beacon_header.slot.into(),
         proposer_index: beacon_header.proposer_index,
         parent_root: beacon_header.parent_root.as_bytes().try_into().map_err(|_| MerkleizationError::InvalidLength)?,
         state_root: beacon_header.state_root.as_bytes().try_into().map_err(|_| MerkleizationError::InvalidLength)?,
@@ -51,23 +52,49 @@ pub fn hash_tree_root_beacon_header(beacon_header: BeaconBlockHeader) -> Result<
     })
 }
 
+/// Hash tree root of a `SyncCommittee` container: the root of its public keys composed with the
+/// root of its aggregate public key.
 pub fn hash_tree_root_sync_committee(sync_committee: SyncCommittee) -> Result<[u8; 32], MerkleizationError> {
-    let mut pubkeys_vec = Vec::new();
+    crate::metrics::timed("Hash tree root", || {
+        let mut node = [0u8; 64];
+        node[..32].copy_from_slice(&hash_tree_root_pubkeys(&sync_committee.pubkeys)?);
+        node[32..].copy_from_slice(&hash_tree_root_pubkey(&sync_committee.aggregate_pubkey));
+        Ok(sha2_256(&node))
+    })
+}
 
-    for pubkey in sync_committee.pubkeys.iter() {
-        let conv_pubkey = Vector::<u8, 48>::from_iter(pubkey.0);
+/// Hash tree root of a 48 byte BLS public key. The key spans two 32 byte chunks, the second
+/// zero padded, and the root is the hash of those chunks.
+pub fn hash_tree_root_pubkey(pubkey: &PublicKey) -> [u8; 32] {
+    let mut chunks = [0u8; 64];
+    chunks[..48].copy_from_slice(&pubkey.0);
+    sha2_256(&chunks)
+}
 
-        pubkeys_vec.push(conv_pubkey);
+/// Hash tree root of a sync committee's public keys, an SSZ `Vector[BLSPubkey, 512]`. The list
+/// must hold exactly 512 keys, so the tree is complete and needs no padding chunks.
+pub fn hash_tree_root_pubkeys(pubkeys: &[PublicKey]) -> Result<[u8; 32], MerkleizationError> {
+    if pubkeys.len() > SYNC_COMMITTEE_SIZE {
+        return Err(MerkleizationError::ListTooLong);
+    }
+    if pubkeys.len() != SYNC_COMMITTEE_SIZE {
+        return Err(MerkleizationError::InvalidLength);
     }
 
-    let pubkeys = Vector::<Vector::<u8, 48>, 512>::from_iter(pubkeys_vec.clone());
-
-    let agg = Vector::<u8, 48>::from_iter(sync_committee.aggregate_pubkey.0);
+    let mut layer: Vec<[u8; 32]> = pubkeys.iter().map(hash_tree_root_pubkey).collect();
+    while layer.len() > 1 {
+        layer = layer
+            .chunks(2)
+            .map(|pair| {
+                let mut node = [0u8; 64];
+                node[..32].copy_from_slice(&pair[0]);
+                node[32..].copy_from_slice(&pair[1]);
+                sha2_256(&node)
+            })
+            .collect();
+    }
 
-    hash_tree_root(SSZSyncCommittee{ 
-        pubkeys: pubkeys, 
-        aggregate_pubkey: agg,
-    })
+    Ok(layer[0])
 }
 
 pub fn hash_tree_root_fork_data(fork_data: ForkData) -> Result<[u8; 32], MerkleizationError> {
@@ -84,8 +111,15 @@ pub fn hash_tree_root_signing_data(signing_data: SigningData) -> Result<[u8; 32]
     })
 }
 
+#[cfg(test)]
+pub fn hash_tree_root_genesis(genesis: crate::Genesis) -> Result<[u8; 32], MerkleizationError> {
+    hash_tree_root(SSZGenesis{
+        validators_root: genesis.validators_root.into(),
+    })
+}
+
 pub fn hash_tree_root<T: SimpleSerializeTrait>(mut object: T) -> Result<[u8; 32], MerkleizationError> {
-    match object.hash_tree_root() {
+    match crate::metrics::timed("Hash tree root", || object.hash_tree_root()) {
         Ok(node)=> node.as_bytes().try_into().map_err(|_| MerkleizationError::HashTreeRootInvalidBytes), 
         Err(_e) => Err(MerkleizationError::HashTreeRootError)
     }
@@ -95,6 +129,7 @@ pub fn hash_tree_root<T: SimpleSerializeTrait>(mut object: T) -> Result<[u8; 32]
 mod tests {
     use crate::merklization;
     use crate as ethereum_beacon_client;
+    use crate::mock::get_initial_sync;
     use frame_support::{assert_ok};
 
     use hex_literal::hex;
@@ -103,7 +138,7 @@ mod tests {
     pub fn test_hash_tree_root_beacon_header() {
         let hash_root = merklization::hash_tree_root_beacon_header(
             ethereum_beacon_client::BeaconBlockHeader {
-                slot: 3,
+                slot: ethereum_beacon_client::Slot(3),
                 proposer_index: 2,
                 parent_root: hex!(
                     "796ea53efb534eab7777809cc5ee2d84e7f25024b9d0c4d7e5bcaab657e4bdbd"
@@ -131,7 +166,7 @@ mod tests {
     pub fn test_hash_tree_root_beacon_header_2() {
         let hash_root = merklization::hash_tree_root_beacon_header(
             ethereum_beacon_client::BeaconBlockHeader {
-                slot: 3476424,
+                slot: ethereum_beacon_client::Slot(3476424),
                 proposer_index: 314905,
                 parent_root: hex!(
                     "c069d7b49cffd2b815b0fb8007eb9ca91202ea548df6f3db60000f29b2489f28"
@@ -684,6 +719,64 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_hash_tree_root_pubkey() {
+        let pubkey = ethereum_beacon_client::PublicKey(hex!("883f0aba4782021304a10a3ce63f8e1d2c31f497ef573eebbabc12538c5aeac6317a4537258963153e47536637d058c0"));
+
+        assert_eq!(
+            merklization::hash_tree_root_pubkey(&pubkey),
+            hex!("33f1b784c0ec25a360911d6f9bed94441ffe0916b9fecbb0c91fac9b95aea8f1")
+        );
+    }
+
+    #[test]
+    pub fn test_hash_tree_root_pubkeys() {
+        // Full 512 member committee, whose root is proven against the fixture's beacon state.
+        let initial_sync = get_initial_sync();
+        let sync_committee = initial_sync.current_sync_committee.clone();
+
+        let pubkeys_root = merklization::hash_tree_root_pubkeys(&sync_committee.pubkeys);
+        assert_ok!(&pubkeys_root);
+        assert_eq!(
+            pubkeys_root.unwrap(),
+            hex!("85362e6a3ea1cd2f3d6aba77bcb200e37044f52a9e977f7189ad32eb39aaa39f")
+        );
+
+        // The committee root is a leaf of the beacon state, so check it against the real
+        // state root with the fixture's branch rather than against another SSZ implementation.
+        let committee_root = merklization::hash_tree_root_sync_committee(sync_committee).unwrap();
+        assert!(ethereum_beacon_client::Pallet::<crate::mock::Test>::is_valid_merkle_branch(
+            committee_root.into(),
+            initial_sync.current_sync_committee_branch,
+            ethereum_beacon_client::CURRENT_SYNC_COMMITTEE_DEPTH,
+            ethereum_beacon_client::CURRENT_SYNC_COMMITTEE_INDEX,
+            initial_sync.header.state_root,
+        ));
+    }
+
+    #[test]
+    pub fn test_hash_tree_root_pubkeys_wrong_length() {
+        let pubkeys = vec![ethereum_beacon_client::PublicKey::default(); 511];
+
+        assert!(merklization::hash_tree_root_pubkeys(&pubkeys).is_err());
+    }
+
+    #[test]
+    pub fn test_hash_tree_root_pubkeys_list_too_long() {
+        let pubkeys = vec![ethereum_beacon_client::PublicKey::default(); 513];
+        assert!(matches!(
+            merklization::hash_tree_root_pubkeys(&pubkeys),
+            Err(merklization::MerkleizationError::ListTooLong)
+        ));
+
+        let mut sync_committee = get_initial_sync().current_sync_committee;
+        sync_committee.pubkeys = pubkeys;
+        assert!(matches!(
+            merklization::hash_tree_root_sync_committee(sync_committee),
+            Err(merklization::MerkleizationError::ListTooLong)
+        ));
+    }
+
     #[test]
     pub fn test_hash_tree_root_fork_data() {
         let hash_root = merklization::hash_tree_root_fork_data(
@@ -715,4 +808,20 @@ mod tests {
             hex!("b9eb2caf2d691b183c2d57f322afe505c078cd08101324f61c3641714789a54e")
         );
     }
+
+    #[test]
+    pub fn test_hash_tree_root_genesis() {
+        let hash_root = merklization::hash_tree_root_genesis(
+            ethereum_beacon_client::Genesis {
+                validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+            }
+        );
+
+        // The root of a container with a single 32 byte field is the field itself.
+        assert_ok!(&hash_root);
+        assert_eq!(
+            hash_root.unwrap(),
+            hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad")
+        );
+    }
 }
\ No newline at end of file
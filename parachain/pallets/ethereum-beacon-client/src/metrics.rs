@@ -0,0 +1,61 @@
+//! Instrumentation of the expensive verification steps, used to size benchmarked weights
+//! against real hardware. Only active in native (`std`) execution, where durations are logged
+//! at debug level and verification outcomes are counted per executing thread. In wasm
+//! everything here is a no-op.
+
+#[cfg(feature = "std")]
+use std::cell::Cell;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct VerificationCounts {
+	pub attempted: u64,
+	pub succeeded: u64,
+	pub failed: u64,
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+	static VERIFICATION_COUNTS: Cell<VerificationCounts> = Cell::new(VerificationCounts::default());
+}
+
+/// Signature verifications performed so far on this thread.
+#[cfg(feature = "std")]
+pub fn verification_counts() -> VerificationCounts {
+	VERIFICATION_COUNTS.with(|counts| counts.get())
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn record_verification(succeeded: bool) {
+	VERIFICATION_COUNTS.with(|counts| {
+		let mut current = counts.get();
+		current.attempted += 1;
+		if succeeded {
+			current.succeeded += 1;
+		} else {
+			current.failed += 1;
+		}
+		counts.set(current);
+	});
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn record_verification(_succeeded: bool) {}
+
+/// Runs `f`, logging how long it took.
+#[cfg(feature = "std")]
+pub(crate) fn timed<R>(label: &str, f: impl FnOnce() -> R) -> R {
+	let start = std::time::Instant::now();
+	let result = f();
+	frame_support::log::debug!(
+		target: "ethereum-beacon-client",
+		"💫 {} took {:?}.",
+		label,
+		start.elapsed()
+	);
+	result
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn timed<R>(_label: &str, f: impl FnOnce() -> R) -> R {
+	f()
+}
@@ -0,0 +1,103 @@
+//! Storage migrations for the beacon client.
+//!
+//! Each migration checks the on-chain storage version before running, so it is safe to leave
+//! in `on_runtime_upgrade` after it has been applied.
+
+use super::*;
+use frame_support::{
+	traits::{Get, GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+use sp_std::collections::btree_map::BTreeMap;
+
+/// Storage version expected by the current code.
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+/// Version 1 adds `LatestFinalizedSlot` and `FinalizedHeaderCountByPeriod`, which are populated
+/// from the existing `FinalizedHeadersBySlot` entries, and `HighestStoredPeriod`, populated from
+/// the existing `SyncCommittees` entries. `ParticipationStatsByPeriod` can't be recovered from
+/// stored data and starts empty.
+pub mod v1 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut reads: Weight = 1;
+		let mut writes: Weight = 3;
+		let mut latest: Option<Slot> = None;
+		let mut counts: BTreeMap<u64, u32> = BTreeMap::new();
+		for slot in <FinalizedHeadersBySlot<T>>::iter_keys() {
+			reads = reads.saturating_add(1);
+			if latest.map_or(true, |latest| slot > latest) {
+				latest = Some(slot);
+			}
+			let count = counts.entry(slot.sync_period()).or_default();
+			*count = count.saturating_add(1);
+		}
+		<LatestFinalizedSlot<T>>::set(latest);
+		for (period, count) in counts {
+			writes = writes.saturating_add(1);
+			<FinalizedHeaderCountByPeriod<T>>::insert(period, count);
+		}
+
+		let mut highest_period = 0;
+		for period in <SyncCommittees<T>>::iter_keys() {
+			reads = reads.saturating_add(1);
+			highest_period = highest_period.max(period);
+		}
+		<HighestStoredPeriod<T>>::put(highest_period);
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		log::info!(
+			target: "ethereum-beacon-client",
+			"💫 Migrated storage to v1, latest finalized slot {:?}, highest stored period {}.",
+			latest,
+			highest_period
+		);
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	#[cfg(any(test, feature = "try-runtime"))]
+	pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return Ok(())
+		}
+		if <LatestFinalizedSlot<T>>::exists() {
+			return Err("LatestFinalizedSlot is already set before migrating to v1")
+		}
+		if <HighestStoredPeriod<T>>::exists() {
+			return Err("HighestStoredPeriod is already set before migrating to v1")
+		}
+		if <FinalizedHeaderCountByPeriod<T>>::iter_keys().next().is_some() {
+			return Err("FinalizedHeaderCountByPeriod is already populated before migrating to v1")
+		}
+
+		Ok(())
+	}
+
+	#[cfg(any(test, feature = "try-runtime"))]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() != 1 {
+			return Err("Storage version is not 1 after migrating")
+		}
+		if <LatestFinalizedSlot<T>>::get() != <FinalizedHeadersBySlot<T>>::iter_keys().max() {
+			return Err("LatestFinalizedSlot does not match the highest stored slot")
+		}
+		if <HighestStoredPeriod<T>>::get() !=
+			<SyncCommittees<T>>::iter_keys().max().unwrap_or_default()
+		{
+			return Err("HighestStoredPeriod does not match the highest stored sync committee")
+		}
+		let headers: u32 = <FinalizedHeaderCountByPeriod<T>>::iter_values().sum();
+		if headers as usize != <FinalizedHeadersBySlot<T>>::iter_keys().count() {
+			return Err("FinalizedHeaderCountByPeriod does not add up to the stored headers")
+		}
+
+		Ok(())
+	}
+}
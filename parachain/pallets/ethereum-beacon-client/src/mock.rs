@@ -1,7 +1,8 @@
 use super::*;
 use crate as ethereum_beacon_client;
-use sp_core::H256;
-use frame_support::parameter_types;
+use snowbridge_core::{MessageDispatch, MessageId, Verifier};
+use sp_core::{H160, H256, U256};
+use frame_support::{parameter_types, traits::EnsureOrigin};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup}, 
 	testing::Header,
@@ -21,6 +22,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Storage, Event<T>},
 		EthereumBeaconClient: ethereum_beacon_client::{Pallet, Call, Config, Storage, Event<T>},
+		BasicInboundChannel: snowbridge_basic_channel::inbound::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -56,8 +58,164 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+parameter_types! {
+	pub const VerifyParentLinkage: bool = true;
+	pub storage RequireEpochAlignedFinalizedSlot: bool = false;
+	pub storage ReuseVerifiedSigningRoots: bool = false;
+	pub const ElectraForkEpoch: u64 = 1_000_000;
+	pub const DenebForkEpoch: u64 = 500_000;
+	pub const GenesisForkVersion: [u8; 4] = [0, 0, 0, 0];
+	pub const DenebForkVersion: [u8; 4] = [4, 0, 0, 0];
+	pub const ElectraForkVersion: [u8; 4] = [5, 0, 0, 0];
+	pub const MaxProofBranchLength: u32 = 9;
+	pub const MaxFailedVerifications: u32 = 2;
+	pub const MaxProposerIndex: u64 = 1_000_000;
+	pub const GenesisTime: u64 = 1616508000;
+	pub const SecondsPerSlot: u64 = 12;
+	// Unbounded by default, since the mock clock starts at zero.
+	pub storage MaxFutureSlots: u64 = u64::MAX;
+	pub storage PeriodBoundarySlots: u64 = 32;
+	pub const FallingBehindThreshold: u64 = 100;
+	pub storage Now: u64 = 0;
+	pub storage AllowedRelayer: Option<u64> = None;
+	pub storage AcceptAllSignatures: bool = false;
+}
+
+thread_local! {
+	static BLS_CALLS: std::cell::RefCell<Vec<(Vec<PublicKey>, H256, Vec<u8>)>> = Default::default();
+	static REWARDED_RELAYERS: std::cell::RefCell<Vec<u64>> = Default::default();
+	static DISPATCHED_MESSAGES: std::cell::RefCell<Vec<(H160, MessageId, Vec<u8>)>> = Default::default();
+}
+
+/// Records the inputs of every verification, and either accepts every signature when
+/// `AcceptAllSignatures` is set or defers to milagro.
+pub struct MockBls;
+
+impl MockBls {
+	pub fn calls() -> Vec<(Vec<PublicKey>, H256, Vec<u8>)> {
+		BLS_CALLS.with(|calls| calls.borrow().clone())
+	}
+}
+
+impl BlsVerify for MockBls {
+	fn fast_aggregate_verify(pubkeys: &[PublicKey], message: H256, signature: &[u8]) -> Result<(), BlsError> {
+		BLS_CALLS.with(|calls| calls.borrow_mut().push((pubkeys.to_vec(), message, signature.to_vec())));
+		if AcceptAllSignatures::get() {
+			return Ok(())
+		}
+		MilagroBls::fast_aggregate_verify(pubkeys, message, signature)
+	}
+}
+
+/// Records every relayer rewarded for a header import.
+pub struct MockRelayerRewards;
+
+impl MockRelayerRewards {
+	pub fn rewarded() -> Vec<u64> {
+		REWARDED_RELAYERS.with(|rewarded| rewarded.borrow().clone())
+	}
+}
+
+impl RewardHeaderImport<u64> for MockRelayerRewards {
+	fn reward(relayer: &u64) {
+		REWARDED_RELAYERS.with(|rewarded| rewarded.borrow_mut().push(*relayer));
+	}
+}
+
+/// Accepts any signed origin, like `EnsureSigned`, unless `AllowedRelayer` restricts
+/// submissions to a single account.
+pub struct EnsureRelayer;
+
+impl EnsureOrigin<Origin> for EnsureRelayer {
+	type Success = u64;
+
+	fn try_origin(o: Origin) -> Result<u64, Origin> {
+		let who = frame_system::EnsureSigned::<u64>::try_origin(o)?;
+		match AllowedRelayer::get() {
+			Some(relayer) if relayer != who => Err(Origin::signed(who)),
+			_ => Ok(who),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::signed(1)
+	}
+}
+
+pub struct MockTime;
+
+impl frame_support::traits::UnixTime for MockTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_secs(Now::get())
+	}
+}
+
 impl ethereum_beacon_client::Config for Test {
 	type Event = Event;
+	type VerifyParentLinkage = VerifyParentLinkage;
+	type RequireEpochAlignedFinalizedSlot = RequireEpochAlignedFinalizedSlot;
+	type ReuseVerifiedSigningRoots = ReuseVerifiedSigningRoots;
+	type ElectraForkEpoch = ElectraForkEpoch;
+	type DenebForkEpoch = DenebForkEpoch;
+	type GenesisForkVersion = GenesisForkVersion;
+	type DenebForkVersion = DenebForkVersion;
+	type ElectraForkVersion = ElectraForkVersion;
+	type TimeProvider = MockTime;
+	type FallingBehindThreshold = FallingBehindThreshold;
+	type MaxProposerIndex = MaxProposerIndex;
+	type MaxProofBranchLength = MaxProofBranchLength;
+	type MaxFailedVerifications = MaxFailedVerifications;
+	type GenesisTime = GenesisTime;
+	type SecondsPerSlot = SecondsPerSlot;
+	type MaxFutureSlots = MaxFutureSlots;
+	type PeriodBoundarySlots = PeriodBoundarySlots;
+	type BlsVerifier = MockBls;
+	type UpdateOrigin = frame_system::EnsureRoot<u64>;
+	type HeaderSubmissionOrigin = EnsureRelayer;
+	type RelayerRewards = MockRelayerRewards;
+}
+
+/// The execution light client isn't part of the mock, so the basic inbound channel can only
+/// accept messages proven against beacon headers.
+pub struct NoExecutionVerifier;
+
+impl Verifier for NoExecutionVerifier {
+	fn verify(_message: &Message) -> Result<Log, DispatchError> {
+		Err(DispatchError::Other("Execution verification is not supported"))
+	}
+
+	fn initialize_storage(_: Vec<snowbridge_ethereum::Header>, _: U256, _: u8) -> Result<(), &'static str> {
+		Ok(())
+	}
+}
+
+/// Records every message dispatched by the basic inbound channel.
+pub struct MockMessageDispatch;
+
+impl MockMessageDispatch {
+	pub fn dispatched() -> Vec<(H160, MessageId, Vec<u8>)> {
+		DISPATCHED_MESSAGES.with(|dispatched| dispatched.borrow().clone())
+	}
+}
+
+impl MessageDispatch<Test, MessageId> for MockMessageDispatch {
+	fn dispatch(source: H160, id: MessageId, payload: &[u8]) {
+		DISPATCHED_MESSAGES.with(|dispatched| dispatched.borrow_mut().push((source, id, payload.to_vec())));
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_dispatch_event(_: MessageId) -> Option<Event> {
+		None
+	}
+}
+
+impl snowbridge_basic_channel::inbound::Config for Test {
+	type Event = Event;
+	type Verifier = NoExecutionVerifier;
+	type BeaconVerifier = EthereumBeaconClient;
+	type MessageDispatch = MockMessageDispatch;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
@@ -67,7 +225,7 @@ pub fn new_tester() -> sp_io::TestExternalities {
 
 pub fn get_initial_sync() -> ethereum_beacon_client::InitialSync {
 	let header: ethereum_beacon_client::BeaconBlockHeader = BeaconBlockHeader{
-		slot: 3476320,
+		slot: Slot(3476320),
 		proposer_index: 168760,
 		parent_root: hex!("244aca04180a684f0af2f18e47e86c55f72e28ec8c1962d538b1b2490af0fbb0").into(),
 		state_root: hex!("c1fbb5f95ce267fe2da3ac96d177558b1a31c27bce5b8aa6d07ffb5233f0fd55").into(),
@@ -609,7 +767,7 @@ pub fn get_initial_sync() -> ethereum_beacon_client::InitialSync {
 pub fn get_committee_sync_period_update() -> ethereum_beacon_client::SyncCommitteePeriodUpdate {
 	let update: ethereum_beacon_client::SyncCommitteePeriodUpdate = SyncCommitteePeriodUpdate{
 		attested_header: BeaconBlockHeader{
-			slot: 200326,
+			slot: Slot(200326),
 			proposer_index: 99434,
 			parent_root: hex!("0206842dc6243ffb36158acaa34c4921d42a891152c727ccd0be96d69bf0fb5c").into(),
 			state_root: hex!("5358f73e377c4896d74a0f3e6d16c8c294edc2b8f179a6df17376fb8eff5eda3").into(),
@@ -1140,7 +1298,7 @@ pub fn get_committee_sync_period_update() -> ethereum_beacon_client::SyncCommitt
 			hex!("6fa71846070803281b4ff1032b3497eb245efce974d857c58b8d1c196af3f784").into(),
 		],
 		finalized_header: BeaconBlockHeader{
-			slot: 200256,
+			slot: Slot(200256),
 			proposer_index: 11784,
 			parent_root: hex!("6eb9432959cb48b147d577823acce25a07423f4564f3f0214b7dbb492bf51eee").into(),
 			state_root: hex!("b818670f535b525153dbf9f683c39ee4ff9a369c3a3d9bdc0cba01e939743e9b").into(),
@@ -1155,7 +1313,7 @@ pub fn get_committee_sync_period_update() -> ethereum_beacon_client::SyncCommitt
 			hex!("896410c29c4030171f3e39931c75929bd05096de19fca3b3677e94b0fa5a892a").into(),
 		],
 		sync_aggregate: SyncAggregate{
-			sync_committee_bits: hex!("ffffe7ffffffdffffdff7ff7fffffefbfcfffffffdffffefffffff6fffff7fb7be7ffdfffffffffeff97ffef3ffffffbff5ffffdffffbf77ffdfffffdfbffdff").into(),
+			sync_committee_bits: SyncCommitteeBits(hex!("ffffe7ffffffdffffdff7ff7fffffefbfcfffffffdffffefffffff6fffff7fb7be7ffdfffffffffeff97ffef3ffffffbff5ffffdffffbf77ffdfffffdfbffdff").to_vec()),
 			sync_committee_signature: hex!("8488a1879136133f11c0cfcc402f16a2ce5d7d6ef164cf9d681133ff4ca5181bf9728c876e9efcbbf81bd95d6a4cc6cd0d06a480f02c3e4b012fe9351eec4b14491929f60fcd5b72da5be4a988d7db7e4e4b7d5ece00a0d32fa1ad4aeaeaed3d").into()
 		},
 		fork_version: hex!("70000071").into(),
@@ -1688,14 +1846,14 @@ pub fn get_current_sync_committee_for_current_committee_update() -> SyncCommitte
 pub fn get_finalized_header_update() -> ethereum_beacon_client::FinalizedHeaderUpdate  {
 	FinalizedHeaderUpdate{
 		attested_header: BeaconBlockHeader{
-			slot: 29122,
+			slot: Slot(29122),
 			proposer_index: 6943,
 			parent_root: hex!("d82627712a10d4be558c82e9638a5fdf4ee9390fdb654168315290366db1d303").into(),
 			state_root: hex!("f935badfc539904d4c46f4fda5f258d5893b63a30295d65c34eba892ac1034d4").into(),
 			body_root: hex!("f459d61290714f581969967c9839042da6a01c71d2e6e714c805c0f7421cfa96").into(),	
 		},
 		finalized_header: BeaconBlockHeader{
-			slot: 29056,
+			slot: Slot(29056),
 			proposer_index: 69405,
 			parent_root: hex!("6d0f66f1a5a243100571591d87b6c2a44d90933f30f93a6b657daed2692bbf3b").into(),
 			state_root: hex!("09638783556b51e185205dae0c9c5a003bc58d51039b9183f6addbdf6bc1ed37").into(),
@@ -1710,7 +1868,7 @@ pub fn get_finalized_header_update() -> ethereum_beacon_client::FinalizedHeaderU
 			hex!("2211dd74d7dbebf22fe050617272dde777b903881aa9c2fe02742a08aa8385f4").into()
 		],	
 		sync_aggregate: SyncAggregate{
-			sync_committee_bits: hex!("febfefffdfdd7ffffdffdfeffffff7fefffdffefffefffffebffffff7bffffdffbffbfbff1ffbdffffffffff6ffdffdfffffffffffffbb7fbfff6ffffeffffff").into(),
+			sync_committee_bits: SyncCommitteeBits(hex!("febfefffdfdd7ffffdffdfeffffff7fefffdffefffefffffebffffff7bffffdffbffbfbff1ffbdffffffffff6ffdffdfffffffffffffbb7fbfff6ffffeffffff").to_vec()),
 			sync_committee_signature: hex!("b3045bd3edfd709fe2d1c094db47def286ca53e67de0535cef14b6a96324c24c1f204d2d315498fade49bef6ddfd58c8063adce1c6dacf2145f75ef8aa79d70abab6f85e20a483e5c7f1f2691ddc3ff7deb0f5adb0118711d8a9b17f778d5ae9").into()
 		},
 		fork_version: hex!("70000071").into()
@@ -2235,4 +2393,115 @@ pub fn get_current_sync_committee_for_finalized_header_update() -> SyncCommittee
 		], 
 		aggregate_pubkey: ethereum_beacon_client::PublicKey(hex!("890d863888696c371f0b815be9967e5a76e8d31327e9fe3cc93097c1b5a9a9da4ad11e9275c09f15955340db0e4bc7b6").into()),
 	}
-}
\ No newline at end of file
+}
+/// A tiny sync committee with deterministic keys, for tests that need headers signed on the fly.
+///
+/// The recorded fixtures above carry full 512 member committees, which makes every signature
+/// check expensive. To produce a minimal signed header instead:
+///
+/// 1. Build any `BeaconBlockHeader`; nothing ties it to a real chain.
+/// 2. Pick the participation bits, one `0`/`1` byte per member of [`minimal_committee::pubkeys`].
+/// 3. Call [`minimal_committee::sign`] with the header, the bits, a fork version and a genesis
+///    validators root. It derives member `i`'s secret key from the big-endian integer `i + 1`,
+///    computes the signing root with `EthereumBeaconClient::compute_signing_root` under the sync
+///    committee domain, and aggregates the participating members' signatures over it.
+/// 4. Pass the same bits, signature, public keys, fork version and validators root to
+///    `EthereumBeaconClient::verify_signed_header`, which runs the real BLS code.
+///
+/// The committee is smaller than `SYNC_COMMITTEE_SIZE`, so these headers are rejected by the
+/// committee and aggregate size checks of the import extrinsics and can only exercise signature
+/// verification directly.
+pub mod minimal_committee {
+	use super::*;
+	use milagro_bls::{AggregateSignature, SecretKey, Signature};
+
+	pub const SIZE: usize = 4;
+
+	fn secret_keys() -> Vec<SecretKey> {
+		(1..=SIZE as u8)
+			.map(|i| {
+				let mut bytes = [0u8; 32];
+				bytes[31] = i;
+				SecretKey::from_bytes(&bytes).unwrap()
+			})
+			.collect()
+	}
+
+	pub fn pubkeys() -> Vec<PublicKey> {
+		secret_keys()
+			.iter()
+			.map(|sk| PublicKey(milagro_bls::PublicKey::from_secret_key(sk).as_bytes()))
+			.collect()
+	}
+
+	/// Signs `header` with every member whose bit is set in `participation`.
+	pub fn sign(
+		header: BeaconBlockHeader,
+		participation: &[u8],
+		fork_version: [u8; 4],
+		validators_root: H256,
+	) -> Vec<u8> {
+		let domain = EthereumBeaconClient::compute_domain(
+			DOMAIN_SYNC_COMMITTEE.to_vec(),
+			Some(fork_version),
+			validators_root,
+		).unwrap();
+		let signing_root = EthereumBeaconClient::compute_signing_root(header, domain).unwrap();
+
+		let mut signature = AggregateSignature::new();
+		for (bit, sk) in participation.iter().zip(secret_keys().iter()) {
+			if *bit == 1 {
+				signature.add(&Signature::new(signing_root.as_bytes(), sk));
+			}
+		}
+
+		signature.as_bytes().to_vec()
+	}
+}
+
+// Execution block and message from the Ropsten London fork, used to check execution proofs.
+pub fn ropsten_london_header() -> snowbridge_ethereum::Header {
+	snowbridge_ethereum::Header {
+		parent_hash: hex!("1026708dc4e90f80898044cd5dcab4f225cc59edd4575c7222a792828d15789c").into(),
+		timestamp: 1629371367u64.into(),
+		number: 10867486u64.into(),
+		author: hex!("9ffed2297c7b81293413550db675073ab46980b2").into(),
+		transactions_root: hex!("d07d104a6f4f0093230be0bfbb69aaa34f7fcf8e84e804b5dc0d12229db2b1f2").into(),
+		ommers_hash: hex!("7f93fe6355b0eeb0c419668cabbbc5ecb42bb9687860293d21d0c1e13f3189be").into(),
+		extra_data: hex!("d883010a07846765746888676f312e31362e35856c696e7578").into(),
+		state_root: hex!("f09fdc13472edc2567917840393e16ca6b215074bba792b26d23217b8ccb726b").into(),
+		receipts_root: hex!("587bceddb4e618b754faf26ab09b1b10fbf957dfc6f0f79207d73e23c4324af9").into(),
+		logs_bloom: (&hex!("102008480008800200004000800000000420001040004002000100110000054000008002000000000000002032000000000000002000000000200102086400800c2900000010420000000008400142240008070090048000006400008020000c0800000002000080010020000000080810800000002c001030442010280000000001810808040001f040000000480400000400010b020008400000411004000002002100002090004801080001000008002400000008000000240000000402020090005200040401400000000002000000100020d002801001002020020860820110002000240000400208020102002102022010002000c80800000000000000")).into(),
+		gas_used: 6102147u64.into(),
+		gas_limit: 8000000u64.into(),
+		difficulty: 1578581203u64.into(),
+		seal: vec![
+				hex!("a050a8258722673f95e2bc00fd71643b77d9821858da66fa5075012f48d1fa0cf2").to_vec(),
+				hex!("88dba6cb08cbe4337b").to_vec(),
+		],
+		base_fee: Some(7u64.into())
+	}
+}
+
+// from https://ropsten.etherscan.io/tx/0x3541903322b74942aa9dd436ac6277d36d874865c35032fe915518d2659fc64c
+pub fn ropsten_london_message() -> snowbridge_core::Message {
+	snowbridge_core::Message {
+		data: hex!("f90119945dd2b8d6f10623426b74d7a92d322f75b74571a3e1a0779b38144a38cfc4351816442048b17fe24ba2b0e0c63446b576e8281160b15bb8e000000000000000000000000000273e201ffb0bccce44560454fb6841429d50710000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000574101ef42cf85be6adf3081ada73af87e27996046fe6300d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d000014bbf08ac602000000000000000000000000000000000000000000000000000000000000000000").to_vec(),
+		proof: snowbridge_core::Proof {
+			block_hash: hex!("a5b871f284c883a67a525e8001a106463234dd968c49eeb300d9382d64f25619").into(),
+			tx_index: 25,
+			data: (
+				vec![
+					hex!("587bceddb4e618b754faf26ab09b1b10fbf957dfc6f0f79207d73e23c4324af9").to_vec(),
+					hex!("e94a68e76d4bb10270ee9f1f50a4155a96ca51bc1f35328113d4c201a25dd8dd").to_vec(),
+					hex!("a90abaedf9feb13afac23e55b89961fc795048f32ceb6502bb94eead9f361a08").to_vec(),
+				],
+				vec![
+					hex!("f871a01392e60e279b56496b25be598f4c7206038bf800589a7c30e86d71554fa41ee9a0e94a68e76d4bb10270ee9f1f50a4155a96ca51bc1f35328113d4c201a25dd8dd808080808080a0e58215be848c1293dd381210359d84485553000a82b67410406d183b42adbbdd8080808080808080").to_vec(),
+					hex!("f90151a03313afdd3bcd74ac9ff430a1739bf4a5a32c4140ee06855ba3e98afb65290bdca06627246dc7d76a237549835b0f6adc480fc1069e9f2bde21d3fc44ab80798695a00ba55e9264f81f6a217f1af33729948632f5cdf04ff2143ef2e952a9462e7bf5a055b2d9e14ac5cbc6871328e071b405ac9a6cf8dad2fa2455ab4a8b960085e441a0d6634b5e368571ef8fc977db39865006084d57a8c27e2ec5ce43851db514ba21a07ef093602d5faf3881949f823cd8f9b65579c73f4578a8b8849316b21ad41308a0edef934e89bb383ea29b324a5744160a706cfd6de092bdb3b7c7ed376016fa89a0fc2e5f1471d608133309328cc7e3dfa6a570c7f1d734f7c7e06dee2bf53f9d47a0531dc644c4c40b4f605b4fdde692b8c180fd6c060f17499fa34a6ffc9a5de8c5a0a90abaedf9feb13afac23e55b89961fc795048f32ceb6502bb94eead9f361a0880808080808080").to_vec(),
+					hex!("f902ca20b902c602f902c201835d1c83b9010000000008000080000000000000000000000000000000400000000000000000000000000000000000000000000200000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000040000000000000020000000000000000000000000000000000000000000000000000000020000000000000000000000000000000100000000004000000000000000000000000000000000000000000000800000000000000000000400000000000000000000000000000000000000000000000f901b7f8999400273e201ffb0bccce44560454fb6841429d5071e1a0caae0f5e72020d428da73a237d1f9bf162e158dda6d4908769b8b60c095b01f4b860000000000000000000000000ef42cf85be6adf3081ada73af87e27996046fe63d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d00000000000000000000000000000000000000000000000002c68af0bb140000f90119945dd2b8d6f10623426b74d7a92d322f75b74571a3e1a0779b38144a38cfc4351816442048b17fe24ba2b0e0c63446b576e8281160b15bb8e000000000000000000000000000273e201ffb0bccce44560454fb6841429d50710000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000574101ef42cf85be6adf3081ada73af87e27996046fe6300d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d000014bbf08ac602000000000000000000000000000000000000000000000000000000000000000000").to_vec(),
+				],
+			),
+		},
+	}
+}
@@ -1,7 +1,9 @@
-use crate::{mock::*, SyncCommittees, Error, BeaconBlockHeader, FinalizedHeaders, FinalizedHeadersBySlot, ChainGenesis, Genesis, PublicKey, merklization};
-use frame_support::{assert_ok, assert_err};
+use crate::{mock::*, Slot, SyncCommittees, SyncCommittee, ForkData, SigningData, Error, BeaconBlockHeader, FinalizedHeaders, FinalizedHeadersBySlot, ChainGenesis, Genesis, PublicKey, merklization, BeaconProof, SYNC_COMMITTEE_SIZE, HeaderStatus, metrics, PublicKeyError, ParticipationStats, ParticipationStatsByPeriod, SyncAggregate, SyncCommitteeBits, FailedVerification, LatestFinalizedSlot};
+use frame_support::{assert_ok, assert_err, assert_noop, traits::OnInitialize};
+use sp_runtime::{DispatchError, Perbill};
 use hex_literal::hex;
-use sp_core::H256;
+use sp_core::{H160, H256};
+use codec::{Decode, Encode};
 
 #[test]
 fn it_syncs_from_an_initial_checkpoint() {
@@ -48,6 +50,98 @@ fn it_updates_a_committee_period_sync_update() {
 	});
 }
 
+#[test]
+fn it_returns_the_next_sync_committee_after_a_period_update() {
+	let update = get_committee_sync_period_update();
+
+	let current_sync_committee = get_current_sync_committee_for_current_committee_update();
+
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
+	new_tester().execute_with(|| {
+		SyncCommittees::<Test>::insert(current_period, current_sync_committee.clone());
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+		assert_eq!(EthereumBeaconClient::next_sync_committee(), None);
+
+		assert_ok!(EthereumBeaconClient::sync_committee_period_update(
+			Origin::signed(1),
+			update.clone(),
+		));
+
+		assert_eq!(EthereumBeaconClient::current_period(), Some(current_period));
+		assert_eq!(EthereumBeaconClient::next_sync_committee(), Some(update.next_sync_committee));
+		assert_eq!(SyncCommittees::<Test>::get(current_period), current_sync_committee);
+	});
+}
+
+#[test]
+fn it_installs_the_next_committee_and_finalizes_the_header_together() {
+	let update = get_committee_sync_period_update();
+	let current_sync_committee = get_current_sync_committee_for_current_committee_update();
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+	let block_root: H256 = merklization::hash_tree_root_beacon_header(update.finalized_header.clone()).unwrap().into();
+
+	new_tester().execute_with(|| {
+		SyncCommittees::<Test>::insert(current_period, current_sync_committee);
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+
+		// A bad signature must leave neither the next committee nor the header behind.
+		let mut bad_update = update.clone();
+		bad_update.sync_aggregate.sync_committee_signature[0] ^= 0xff;
+		assert!(EthereumBeaconClient::sync_committee_period_update(Origin::signed(1), bad_update).is_err());
+		assert!(!<SyncCommittees<Test>>::contains_key(current_period + 1));
+		assert!(!<FinalizedHeaders<Test>>::contains_key(block_root));
+
+		assert_ok!(EthereumBeaconClient::sync_committee_period_update(Origin::signed(1), update.clone()));
+		assert_eq!(<SyncCommittees<Test>>::get(current_period + 1), update.next_sync_committee);
+		assert_eq!(<FinalizedHeadersBySlot<Test>>::get(update.finalized_header.slot), Some(block_root));
+		assert!(<FinalizedHeaders<Test>>::contains_key(block_root));
+	});
+}
+
+#[test]
+fn it_applies_period_updates_in_order_until_the_first_failure() {
+	let update = get_committee_sync_period_update();
+	let current_sync_committee = get_current_sync_committee_for_current_committee_update();
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
+	// Follows `update`, but its next sync committee proof doesn't check out.
+	let mut bad_update = update.clone();
+	bad_update.sync_committee_period += 1;
+	bad_update.next_sync_committee_branch[0] = H256::repeat_byte(0xff);
+
+	new_tester().execute_with(|| {
+		System::set_block_number(1);
+		SyncCommittees::<Test>::insert(current_period, current_sync_committee);
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+
+		// Only a failing update: nothing to keep, so the call fails.
+		assert!(EthereumBeaconClient::sync_committee_period_updates(
+			Origin::signed(1),
+			vec![bad_update.clone()],
+		).is_err());
+
+		// Submitted out of order, but applied in period order.
+		assert_ok!(EthereumBeaconClient::sync_committee_period_updates(
+			Origin::signed(1),
+			vec![bad_update, update.clone()],
+		));
+
+		System::assert_last_event(
+			crate::Event::<Test>::SyncCommitteePeriodUpdatesApplied { count: 1 }.into()
+		);
+		assert_eq!(<SyncCommittees<Test>>::get(current_period + 1), update.next_sync_committee);
+		assert!(<FinalizedHeadersBySlot<Test>>::contains_key(update.finalized_header.slot));
+		assert!(!<SyncCommittees<Test>>::contains_key(current_period + 2));
+	});
+}
+
 #[test]
 fn it_processes_a_finalized_header_update() {
 	let update = get_finalized_header_update();
@@ -73,179 +167,557 @@ fn it_processes_a_finalized_header_update() {
 }
 
 #[test]
-fn it_errors_when_importing_a_header_with_no_sync_commitee_for_period() {
-	let update = get_finalized_header_update();
+fn it_runs_the_full_sync_flow() {
+	let initial_sync = get_initial_sync();
+	let period_update = get_committee_sync_period_update();
+	let finalized_header_update = get_finalized_header_update();
 
 	new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::initial_sync(Origin::signed(1), initial_sync.clone()));
+
+		let initial_period = EthereumBeaconClient::compute_current_sync_period(initial_sync.header.slot);
+		assert_eq!(SyncCommittees::<Test>::get(initial_period), initial_sync.current_sync_committee);
+
+		// The initial sync fixture and the update fixtures were captured from different
+		// networks, so the committees and genesis root a contiguous sync would have stored
+		// for the update fixtures are seeded here.
 		ChainGenesis::<Test>::set(Genesis{
 			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
 		});
+		SyncCommittees::<Test>::insert(
+			EthereumBeaconClient::compute_current_sync_period(period_update.attested_header.slot),
+			get_current_sync_committee_for_current_committee_update(),
+		);
+		SyncCommittees::<Test>::insert(
+			EthereumBeaconClient::compute_current_sync_period(finalized_header_update.attested_header.slot),
+			get_current_sync_committee_for_finalized_header_update(),
+		);
 
-		assert_err!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update), Error::<Test>::SyncCommitteeMissing);
+		assert_ok!(EthereumBeaconClient::sync_committee_period_update(Origin::signed(1), period_update.clone()));
+
+		let next_period = EthereumBeaconClient::compute_current_sync_period(period_update.attested_header.slot) + 1;
+		assert_eq!(SyncCommittees::<Test>::get(next_period), period_update.next_sync_committee);
+
+		assert_ok!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), finalized_header_update.clone()));
+
+		for header in [initial_sync.header, period_update.finalized_header, finalized_header_update.finalized_header] {
+			let block_root: H256 = merklization::hash_tree_root_beacon_header(header.clone()).unwrap().into();
+
+			assert_eq!(<FinalizedHeaders<Test>>::get(block_root), Some(header.clone()));
+			assert_eq!(<FinalizedHeadersBySlot<Test>>::get(header.slot), Some(block_root));
+		}
 	});
 }
 
 #[test]
-fn it_converts_to_binary_kiln_update() {
-	let result = EthereumBeaconClient::convert_to_binary(hex!("bffffffff7f5ffdfcfeffeffbfdffffbfffffdffffefefffdffff7ffffff77fffdf7bff77ffdf7fffafffffff77fefffeff7fffffffff5f7ffdfffdfbfddfffb").to_vec());
+fn it_checks_the_finality_proof_before_the_next_sync_committee_proof() {
+	let mut update = get_committee_sync_period_update();
+	update.finality_branch[0] = H256::repeat_byte(1);
+	update.next_sync_committee_branch[0] = H256::repeat_byte(1);
 
-	assert_eq!(
-		result,
-		vec![
-			1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 0, 0, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1			]);
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
+	new_tester().execute_with(|| {
+		SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_current_committee_update());
+
+		assert_err!(
+			EthereumBeaconClient::sync_committee_period_update(Origin::signed(1), update.clone()),
+			Error::<Test>::InvalidHeaderMerkleProof
+		);
+		assert_eq!(SyncCommittees::<Test>::try_get(current_period + 1), Err(()));
+	});
 }
 
 #[test]
-pub fn test_get_sync_committee_sum() {
+fn it_processes_a_finalized_header_update_signed_by_the_next_sync_committee() {
+	let update = get_finalized_header_update();
+
+	let current_sync_committee = get_current_sync_committee_for_current_committee_update();
+	let next_sync_committee = get_current_sync_committee_for_finalized_header_update();
+
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
 	new_tester().execute_with(|| {
-		assert_eq!(
-			EthereumBeaconClient::get_sync_committee_sum(vec![0, 1, 0, 1, 1, 0, 1, 0, 1]),
-			5
-		);
+		SyncCommittees::<Test>::insert(current_period, current_sync_committee);
+		SyncCommittees::<Test>::insert(current_period + 1, next_sync_committee);
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+		// The fixture isn't near the end of its period, so widen the boundary to cover it.
+		PeriodBoundarySlots::set(&crate::slots_per_period());
+
+		let post_info = EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()).unwrap();
+
+		let block_root: H256 = merklization::hash_tree_root_beacon_header(update.finalized_header.clone()).unwrap().into();
+
+		assert!(<FinalizedHeaders<Test>>::contains_key(block_root));
+		// Both signature verifications are charged.
+		assert_eq!(post_info.actual_weight, None);
 	});
 }
 
 #[test]
-pub fn test_compute_domain() {
+fn it_does_not_try_the_next_sync_committee_away_from_a_period_boundary() {
+	let update = get_finalized_header_update();
+
+	let current_sync_committee = get_current_sync_committee_for_current_committee_update();
+	let next_sync_committee = get_current_sync_committee_for_finalized_header_update();
+
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
 	new_tester().execute_with(|| {
-		let domain = EthereumBeaconClient::compute_domain(
-			hex!("07000000").into(),
-			hex!("00000001").into(),
-			hex!("5dec7ae03261fde20d5b024dfabce8bac3276c9a4908e23d50ba8c9b50b0adff").into(),
-		);
+		SyncCommittees::<Test>::insert(current_period, current_sync_committee);
+		SyncCommittees::<Test>::insert(current_period + 1, next_sync_committee);
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
 
-		assert_ok!(&domain);
-		assert_eq!(
-			domain.unwrap(),
-			hex!("0700000046324489ceb6ada6d118eacdbe94f49b1fcb49d5481a685979670c7c").into()
-		);
+		assert!(!EthereumBeaconClient::is_near_period_end(update.attested_header.slot, current_period));
+		assert_err!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update), Error::<Test>::SignatureVerificationFailed);
 	});
 }
 
 #[test]
-pub fn test_compute_domain_kiln() {
+fn test_is_near_period_end() {
 	new_tester().execute_with(|| {
-		let domain = EthereumBeaconClient::compute_domain(
-			hex!("07000000").into(),
-			hex!("70000071").into(),
-			hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
-		);
+		let end = crate::period_end_slot(3).0;
 
-		assert_ok!(&domain);
-		assert_eq!(
-			domain.unwrap(),
-			hex!("07000000e7acb21061790987fa1c1e745cccfb358370b33e8af2b2c18938e6c2").into()
-		);
+		assert!(EthereumBeaconClient::is_near_period_end(Slot(end), 3));
+		assert!(EthereumBeaconClient::is_near_period_end(Slot(end - 31), 3));
+		assert!(!EthereumBeaconClient::is_near_period_end(Slot(end - 32), 3));
+		assert!(!EthereumBeaconClient::is_near_period_end(crate::period_start_slot(3), 3));
 	});
 }
 
 #[test]
-pub fn test_compute_signing_root_bls() {
+fn it_errors_when_importing_a_header_not_signed_by_a_known_sync_committee() {
+	let update = get_finalized_header_update();
+
+	let current_sync_committee = get_current_sync_committee_for_current_committee_update();
+
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
 	new_tester().execute_with(|| {
-		let signing_root = EthereumBeaconClient::compute_signing_root(
-			BeaconBlockHeader {
-				slot: 3529537,
-				proposer_index: 192549,
-				parent_root: hex!(
-					"1f8dc05ea427f78e84e2e2666e13c3befb7106fd1d40ef8a3f67cf615f3f2a4c"
-				)
-				.into(),
-				state_root: hex!(
-					"0dfb492a83da711996d2d76b64604f9bca9dc08b6c13cf63b3be91742afe724b"
-				)
-				.into(),
-				body_root: hex!("66fba38f7c8c2526f7ddfe09c1a54dd12ff93bdd4d0df6a0950e88e802228bfa")
-					.into(),
-			},
-			hex!("07000000afcaaba0efab1ca832a15152469bb09bb84641c405171dfa2d3fb45f").into(),
-		);
+		SyncCommittees::<Test>::insert(current_period, current_sync_committee);
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
 
-		assert_ok!(&signing_root);
-		assert_eq!(
-			signing_root.unwrap(),
-			hex!("3ff6e9807da70b2f65cdd58ea1b25ed441a1d589025d2c4091182026d7af08fb").into()
-		);
+		assert_err!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update), Error::<Test>::SignatureVerificationFailed);
 	});
 }
 
 #[test]
-pub fn test_compute_signing_root_kiln() {
+fn it_records_failed_signature_verifications() {
+	let update = get_finalized_header_update();
+
+	let current_sync_committee = get_current_sync_committee_for_current_committee_update();
+
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+	let failure = |relayer| FailedVerification {
+		slot: update.finalized_header.slot,
+		relayer,
+		reason: Error::<Test>::SignatureVerificationFailed.into(),
+	};
+
 	new_tester().execute_with(|| {
-		let signing_root = EthereumBeaconClient::compute_signing_root(
-			BeaconBlockHeader {
-				slot: 221316,
-				proposer_index: 79088,
-				parent_root: hex!(
-					"b4c15cd79da1a4e645b0104fa66d226cb6dce0fae3522789cc4d0b3ae41d96f7"
-				)
-				.into(),
-				state_root: hex!(
-					"6f711ef2e36decbc8f7037e73bbdace42c11f2896a43e44ab8a78dcb2ba66122"
-				)
-				.into(),
-				body_root: hex!("963eaa01341c16dc8f288da47eedad0792978fdaab9f1f97ae0a1103494d1a10")
-					.into(),
-			},
-			hex!("07000000afcaaba0efab1ca832a15152469bb09bb84641c405171dfa2d3fb45f").into(),
-		);
+		SyncCommittees::<Test>::insert(current_period, current_sync_committee);
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
 
-		assert_ok!(&signing_root);
-		assert_eq!(
-			signing_root.unwrap(),
-			hex!("4ce7b4192c0292a2bbf4107766ddc0f613261bb8e6968ccd0e6b71b30fad6d7c").into()
-		);
+		assert_err!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()), Error::<Test>::SignatureVerificationFailed);
+		assert_eq!(EthereumBeaconClient::failed_verifications(), vec![failure(1)]);
+
+		// The oldest entry is evicted once MaxFailedVerifications is reached.
+		assert_err!(EthereumBeaconClient::import_finalized_header(Origin::signed(2), update.clone()), Error::<Test>::SignatureVerificationFailed);
+		assert_err!(EthereumBeaconClient::import_finalized_header(Origin::signed(3), update.clone()), Error::<Test>::SignatureVerificationFailed);
+		assert_eq!(EthereumBeaconClient::failed_verifications(), vec![failure(2), failure(3)]);
+
+		let block_root: H256 = merklization::hash_tree_root_beacon_header(update.finalized_header.clone()).unwrap().into();
+		assert!(!<FinalizedHeaders<Test>>::contains_key(block_root));
 	});
 }
 
 #[test]
-pub fn test_compute_signing_root_kiln_head_update() {
+fn it_rewards_the_relayer_of_an_imported_finalized_header() {
+	let update = get_finalized_header_update();
+
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
 	new_tester().execute_with(|| {
-		let signing_root = EthereumBeaconClient::compute_signing_root(
-			BeaconBlockHeader {
-				slot: 222472,
-				proposer_index: 10726,
-				parent_root: hex!(
-					"5d481a9721f0ecce9610eab51d400d223683d599b7fcebca7e4c4d10cdef6ebb"
-				)
-				.into(),
-				state_root: hex!(
-					"14eb4575895f996a84528b789ff2e4d5148242e2983f03068353b2c37015507a"
-				)
-				.into(),
-				body_root: hex!("7bb669c75b12e0781d6fa85d7fc2f32d64eafba89f39678815b084c156e46cac")
-					.into(),
-			},
-			hex!("07000000e7acb21061790987fa1c1e745cccfb358370b33e8af2b2c18938e6c2").into(),
-		);
+		SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_finalized_header_update());
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
 
-		assert_ok!(&signing_root);
-		assert_eq!(
-			signing_root.unwrap(),
-			hex!("da12b6a6d3516bc891e8a49f82fc1925cec40b9327e06457f695035303f55cd8").into()
-		);
+		let mut unsigned = update.clone();
+		unsigned.sync_aggregate.sync_committee_bits = SyncCommitteeBits(vec![0; unsigned.sync_aggregate.sync_committee_bits.0.len()]);
+		assert!(EthereumBeaconClient::import_finalized_header(Origin::signed(2), unsigned).is_err());
+		assert_eq!(MockRelayerRewards::rewarded(), vec![]);
+
+		assert_ok!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update));
+		assert_eq!(MockRelayerRewards::rewarded(), vec![1]);
 	});
 }
 
+#[test]
+fn it_does_not_reward_importing_a_header_behind_the_latest_finalized_slot() {
+	let update = get_finalized_header_update();
+
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
+	new_tester().execute_with(|| {
+		SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_finalized_header_update());
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+		let latest_slot = Slot(update.finalized_header.slot.0 + 32);
+		<LatestFinalizedSlot<Test>>::put(latest_slot);
+
+		assert_ok!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update));
+		assert_eq!(MockRelayerRewards::rewarded(), vec![]);
+		assert_eq!(<LatestFinalizedSlot<Test>>::get(), Some(latest_slot));
+	});
+}
 
 #[test]
-pub fn test_compute_domain_bls() {
+fn it_rejects_a_finalized_header_update_without_participants_before_verifying_it() {
+	let mut update = get_finalized_header_update();
+	update.sync_aggregate.sync_committee_bits = SyncCommitteeBits(vec![0; SYNC_COMMITTEE_SIZE / 8]);
+
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
 	new_tester().execute_with(|| {
-		let domain = EthereumBeaconClient::compute_domain(
-			hex!("07000000").into(),
-			hex!("01000000").into(),
-			hex!("4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95").into(),
-		);
+		SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_finalized_header_update());
 
-		assert_ok!(&domain);
-		assert_eq!(
-			domain.unwrap(),
-			hex!("07000000afcaaba0efab1ca832a15152469bb09bb84641c405171dfa2d3fb45f").into()
+		assert_err!(
+			EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()),
+			Error::<Test>::InsufficientSyncCommitteeParticipants
 		);
+		assert!(MockBls::calls().is_empty());
+		assert_eq!(EthereumBeaconClient::verified_header(update.finalized_header.slot), None);
 	});
 }
 
 #[test]
-pub fn test_is_valid_merkle_proof() {
+fn it_rejects_a_finalized_header_not_before_its_attested_header() {
+	let update = get_finalized_header_update();
+	assert!(update.finalized_header.slot < update.attested_header.slot);
+
+	new_tester().execute_with(|| {
+		let mut same_slot = update.clone();
+		same_slot.attested_header.slot = update.finalized_header.slot;
+		assert_err!(
+			EthereumBeaconClient::import_finalized_header(Origin::signed(1), same_slot),
+			Error::<Test>::FinalizedHeaderNotBeforeAttested
+		);
+
+		let mut later_slot = update.clone();
+		later_slot.attested_header.slot = Slot(update.finalized_header.slot.0 - 1);
+		assert_err!(
+			EthereumBeaconClient::import_finalized_header(Origin::signed(1), later_slot),
+			Error::<Test>::FinalizedHeaderNotBeforeAttested
+		);
+	});
+}
+
+#[test]
+fn it_rejects_a_finalized_header_from_the_future() {
+	let update = get_finalized_header_update();
+
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+	let slot_time = |slot: u64| GenesisTime::get() + slot * SecondsPerSlot::get();
+
+	new_tester().execute_with(|| {
+		SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_finalized_header_update());
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+		MaxFutureSlots::set(&32);
+
+		Now::set(&slot_time(update.finalized_header.slot.0 - 1000));
+		assert_err!(
+			EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()),
+			Error::<Test>::HeaderFromFuture
+		);
+
+		// Within the tolerance of the expected slot.
+		Now::set(&slot_time(update.finalized_header.slot.0 - 32));
+		assert_ok!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update));
+	});
+}
+
+#[test]
+fn it_errors_when_importing_a_header_with_no_sync_commitee_for_period() {
+	let update = get_finalized_header_update();
+
+	new_tester().execute_with(|| {
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+
+		assert_err!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update), Error::<Test>::SyncCommitteeMissing);
+	});
+}
+
+#[test]
+fn it_flags_whether_a_failed_finalized_header_import_is_retryable() {
+	let update = get_finalized_header_update();
+	let slot = update.finalized_header.slot;
+
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
+	new_tester().execute_with(|| {
+		System::set_block_number(1);
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+
+		// The committee may still be installed.
+		assert_err!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()), Error::<Test>::SyncCommitteeMissing);
+		System::assert_last_event(crate::Event::<Test>::FinalizedHeaderImportFailed { slot, retryable: true }.into());
+
+		// A bad signature stays bad.
+		SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_current_committee_update());
+		assert_err!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()), Error::<Test>::SignatureVerificationFailed);
+		System::assert_last_event(crate::Event::<Test>::FinalizedHeaderImportFailed { slot, retryable: false }.into());
+
+		// So does a bad finality proof.
+		let mut bad_proof = update;
+		bad_proof.finality_branch[0] = H256::repeat_byte(1);
+		assert_err!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), bad_proof), Error::<Test>::InvalidHeaderMerkleProof);
+		System::assert_last_event(crate::Event::<Test>::FinalizedHeaderImportFailed { slot, retryable: false }.into());
+	});
+}
+
+#[test]
+fn it_expands_sync_committee_bits_kiln_update() {
+	let result = SyncCommitteeBits(hex!("bffffffff7f5ffdfcfeffeffbfdffffbfffffdffffefefffdffff7ffffff77fffdf7bff77ffdf7fffafffffff77fefffeff7fffffffff5f7ffdfffdfbfddfffb").to_vec()).iter_bits().collect::<Vec<u8>>();
+
+	assert_eq!(
+		result,
+		vec![
+			1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 0, 0, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1			]);
+}
+
+#[test]
+pub fn test_get_sync_committee_sum() {
+	new_tester().execute_with(|| {
+		assert_eq!(
+			EthereumBeaconClient::get_sync_committee_sum(vec![0, 1, 0, 1, 1, 0, 1, 0, 1]),
+			Ok(5)
+		);
+	});
+}
+
+#[test]
+pub fn test_get_sync_committee_sum_empty() {
+	new_tester().execute_with(|| {
+		assert_err!(
+			EthereumBeaconClient::get_sync_committee_sum(vec![]),
+			Error::<Test>::InvalidSyncCommitteeBits
+		);
+	});
+}
+
+#[test]
+pub fn test_get_sync_committee_sum_non_bit_value() {
+	new_tester().execute_with(|| {
+		assert_err!(
+			EthereumBeaconClient::get_sync_committee_sum(vec![0, 1, 2, 1]),
+			Error::<Test>::InvalidSyncCommitteeBits
+		);
+	});
+}
+
+#[test]
+pub fn test_compute_domain() {
+	new_tester().execute_with(|| {
+		let domain = EthereumBeaconClient::compute_domain(
+			hex!("07000000").into(),
+			hex!("00000001").into(),
+			hex!("5dec7ae03261fde20d5b024dfabce8bac3276c9a4908e23d50ba8c9b50b0adff").into(),
+		);
+
+		assert_ok!(&domain);
+		assert_eq!(
+			domain.unwrap(),
+			hex!("0700000046324489ceb6ada6d118eacdbe94f49b1fcb49d5481a685979670c7c").into()
+		);
+	});
+}
+
+#[test]
+pub fn test_compute_domain_genesis_fork_version() {
+	new_tester().execute_with(|| {
+		// Mainnet genesis validators root. The domain embeds the genesis fork digest, 0xb5303f2a.
+		let domain = EthereumBeaconClient::compute_domain(
+			hex!("07000000").into(),
+			None,
+			hex!("4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95").into(),
+		);
+
+		assert_ok!(&domain);
+		assert_eq!(
+			domain.unwrap(),
+			hex!("07000000b5303f2ad2010d699a76c8e62350947421a3e4a979779642cfdb0f66").into()
+		);
+	});
+}
+
+#[test]
+pub fn test_compute_domain_kiln() {
+	new_tester().execute_with(|| {
+		let domain = EthereumBeaconClient::compute_domain(
+			hex!("07000000").into(),
+			hex!("70000071").into(),
+			hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		);
+
+		assert_ok!(&domain);
+		assert_eq!(
+			domain.unwrap(),
+			hex!("07000000e7acb21061790987fa1c1e745cccfb358370b33e8af2b2c18938e6c2").into()
+		);
+	});
+}
+
+#[test]
+pub fn test_compute_domain_rejects_short_domain_type() {
+	new_tester().execute_with(|| {
+		assert_err!(
+			EthereumBeaconClient::compute_domain(
+				hex!("070000").into(),
+				hex!("00000001").into(),
+				hex!("5dec7ae03261fde20d5b024dfabce8bac3276c9a4908e23d50ba8c9b50b0adff").into(),
+			),
+			Error::<Test>::InvalidDomainType
+		);
+	});
+}
+
+#[test]
+pub fn test_compute_domain_rejects_long_domain_type() {
+	new_tester().execute_with(|| {
+		assert_err!(
+			EthereumBeaconClient::compute_domain(
+				hex!("0700000000").into(),
+				hex!("00000001").into(),
+				hex!("5dec7ae03261fde20d5b024dfabce8bac3276c9a4908e23d50ba8c9b50b0adff").into(),
+			),
+			Error::<Test>::InvalidDomainType
+		);
+	});
+}
+
+#[test]
+pub fn test_compute_signing_root_bls() {
+	new_tester().execute_with(|| {
+		let signing_root = EthereumBeaconClient::compute_signing_root(
+			BeaconBlockHeader {
+				slot: Slot(3529537),
+				proposer_index: 192549,
+				parent_root: hex!(
+					"1f8dc05ea427f78e84e2e2666e13c3befb7106fd1d40ef8a3f67cf615f3f2a4c"
+				)
+				.into(),
+				state_root: hex!(
+					"0dfb492a83da711996d2d76b64604f9bca9dc08b6c13cf63b3be91742afe724b"
+				)
+				.into(),
+				body_root: hex!("66fba38f7c8c2526f7ddfe09c1a54dd12ff93bdd4d0df6a0950e88e802228bfa")
+					.into(),
+			},
+			hex!("07000000afcaaba0efab1ca832a15152469bb09bb84641c405171dfa2d3fb45f").into(),
+		);
+
+		assert_ok!(&signing_root);
+		assert_eq!(
+			signing_root.unwrap(),
+			hex!("3ff6e9807da70b2f65cdd58ea1b25ed441a1d589025d2c4091182026d7af08fb").into()
+		);
+	});
+}
+
+#[test]
+pub fn test_compute_signing_root_kiln() {
+	new_tester().execute_with(|| {
+		let signing_root = EthereumBeaconClient::compute_signing_root(
+			BeaconBlockHeader {
+				slot: Slot(221316),
+				proposer_index: 79088,
+				parent_root: hex!(
+					"b4c15cd79da1a4e645b0104fa66d226cb6dce0fae3522789cc4d0b3ae41d96f7"
+				)
+				.into(),
+				state_root: hex!(
+					"6f711ef2e36decbc8f7037e73bbdace42c11f2896a43e44ab8a78dcb2ba66122"
+				)
+				.into(),
+				body_root: hex!("963eaa01341c16dc8f288da47eedad0792978fdaab9f1f97ae0a1103494d1a10")
+					.into(),
+			},
+			hex!("07000000afcaaba0efab1ca832a15152469bb09bb84641c405171dfa2d3fb45f").into(),
+		);
+
+		assert_ok!(&signing_root);
+		assert_eq!(
+			signing_root.unwrap(),
+			hex!("4ce7b4192c0292a2bbf4107766ddc0f613261bb8e6968ccd0e6b71b30fad6d7c").into()
+		);
+	});
+}
+
+#[test]
+pub fn test_compute_signing_root_kiln_head_update() {
+	new_tester().execute_with(|| {
+		let signing_root = EthereumBeaconClient::compute_signing_root(
+			BeaconBlockHeader {
+				slot: Slot(222472),
+				proposer_index: 10726,
+				parent_root: hex!(
+					"5d481a9721f0ecce9610eab51d400d223683d599b7fcebca7e4c4d10cdef6ebb"
+				)
+				.into(),
+				state_root: hex!(
+					"14eb4575895f996a84528b789ff2e4d5148242e2983f03068353b2c37015507a"
+				)
+				.into(),
+				body_root: hex!("7bb669c75b12e0781d6fa85d7fc2f32d64eafba89f39678815b084c156e46cac")
+					.into(),
+			},
+			hex!("07000000e7acb21061790987fa1c1e745cccfb358370b33e8af2b2c18938e6c2").into(),
+		);
+
+		assert_ok!(&signing_root);
+		assert_eq!(
+			signing_root.unwrap(),
+			hex!("da12b6a6d3516bc891e8a49f82fc1925cec40b9327e06457f695035303f55cd8").into()
+		);
+	});
+}
+
+
+#[test]
+pub fn test_compute_domain_bls() {
+	new_tester().execute_with(|| {
+		let domain = EthereumBeaconClient::compute_domain(
+			hex!("07000000").into(),
+			hex!("01000000").into(),
+			hex!("4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95").into(),
+		);
+
+		assert_ok!(&domain);
+		assert_eq!(
+			domain.unwrap(),
+			hex!("07000000afcaaba0efab1ca832a15152469bb09bb84641c405171dfa2d3fb45f").into()
+		);
+	});
+}
+
+#[test]
+pub fn test_is_valid_merkle_proof() {
 	new_tester().execute_with(|| {
 		assert_eq!(
 			EthereumBeaconClient::is_valid_merkle_branch(
@@ -352,10 +824,29 @@ pub fn test_bls_fast_aggregate_verify_invalid_signature() {
 	});
 }
 
+#[test]
+pub fn test_bls_fast_aggregate_verify_malformed_inputs() {
+	new_tester().execute_with(|| {
+		let pubkey = PublicKey(hex!("a73eb991aa22cdb794da6fcde55a427f0a4df5a4a70de23a988b5e5fc8c4d844f66d990273267a54dd21579b7ba6a086").into());
+		let message: H256 = hex!("99241e7146cdcc5a5ddc9a60bab8f378c0271e548065a38bcc60624e1dbed97f").into();
+		let signature = hex!("b204e9656cbeb79a9a8e397920fd8e60c5f5d9443f58d42186f773c6ade2bd263e2fe6dbdc47f148f871ed9a00b8ac8b17a40d65c8d02120c00dca77495888366b4ccc10f1c6daa02db6a7516555ca0665bca92a647b5f3a514fa083fdc53b6e").to_vec();
+
+		assert_err!(
+			EthereumBeaconClient::bls_fast_aggregate_verify(vec![pubkey.clone()], message, vec![]),
+			Error::<Test>::InvalidSignature
+		);
+		assert_err!(
+			EthereumBeaconClient::bls_fast_aggregate_verify(vec![pubkey.clone()], message, signature[..48].to_vec()),
+			Error::<Test>::InvalidSignature
+		);
+		assert!(EthereumBeaconClient::bls_fast_aggregate_verify(vec![PublicKey([0xff; 48])], message, signature).is_err());
+	});
+}
+
 #[test]
 pub fn test_bls_fast_aggregate_verify_kiln_head_update() {
 	new_tester().execute_with(|| {
-		let sync_committee_bits = EthereumBeaconClient::convert_to_binary(hex!("bffffffff7f1ffdfcfeffeffbfdffffbfffffdffffefefffdffff7f7ffff77fffdf7bff77ffdf7fffafffffff77fefffeff7effffffff5f7fedfffdfb6ddff7b").into());
+		let sync_committee_bits = SyncCommitteeBits(hex!("bffffffff7f1ffdfcfeffeffbfdffffbfffffdffffefefffdffff7f7ffff77fffdf7bff77ffdf7fffafffffff77fefffeff7effffffff5f7fedfffdfb6ddff7b").to_vec()).iter_bits().collect();
 
 		assert_ok!(EthereumBeaconClient::verify_signed_header(
 			sync_committee_bits,
@@ -876,7 +1367,7 @@ pub fn test_bls_fast_aggregate_verify_kiln_head_update() {
 			],
 			hex!("70000071").into(),
 			BeaconBlockHeader{
-				slot: 222472,
+				slot: Slot(222472),
 				proposer_index: 10726,
 				parent_root: hex!("5d481a9721f0ecce9610eab51d400d223683d599b7fcebca7e4c4d10cdef6ebb").into(),
 				state_root: hex!("14eb4575895f996a84528b789ff2e4d5148242e2983f03068353b2c37015507a").into(),
@@ -888,12 +1379,38 @@ pub fn test_bls_fast_aggregate_verify_kiln_head_update() {
 }
 
 #[test]
-pub fn test_sync_committee_participation_is_supermajority() {
+pub fn test_expand_sync_committee_bits() {
 	new_tester().execute_with(|| {
-		let sync_committee_bits = EthereumBeaconClient::convert_to_binary(hex!("bffffffff7f1ffdfcfeffeffbfdffffbfffffdffffefefffdffff7f7ffff77fffdf7bff77ffdf7fffafffffff77fefffeff7effffffff5f7fedfffdfb6ddff7b").into());
+		let bits = EthereumBeaconClient::expand_sync_committee_bits(&SyncCommitteeBits(vec![0xff; 64]));
+		assert_ok!(&bits);
+		assert_eq!(bits.unwrap().len(), SYNC_COMMITTEE_SIZE);
 
-		assert_ok!(EthereumBeaconClient::sync_committee_participation_is_supermajority(sync_committee_bits));
-	});
+		assert_err!(
+			EthereumBeaconClient::expand_sync_committee_bits(&SyncCommitteeBits(vec![0xff; 65])),
+			Error::<Test>::InvalidSyncCommitteeBitsLength
+		);
+		assert_err!(
+			EthereumBeaconClient::expand_sync_committee_bits(&SyncCommitteeBits(vec![0xff; 63])),
+			Error::<Test>::InvalidSyncCommitteeBitsLength
+		);
+	});
+}
+
+#[test]
+pub fn test_sync_committee_bits_are_expanded_least_significant_bit_first() {
+	// Committee members 0 and 15 participated.
+	let bits = SyncCommitteeBits(vec![0x01, 0x80]);
+
+	assert_eq!(bits.iter_bits().collect::<Vec<u8>>(), vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+}
+
+#[test]
+pub fn test_sync_committee_participation_is_supermajority() {
+	new_tester().execute_with(|| {
+		let sync_committee_bits = SyncCommitteeBits(hex!("bffffffff7f1ffdfcfeffeffbfdffffbfffffdffffefefffdffff7f7ffff77fffdf7bff77ffdf7fffafffffff77fefffeff7effffffff5f7fedfffdfb6ddff7b").to_vec()).iter_bits().collect();
+
+		assert_ok!(EthereumBeaconClient::sync_committee_participation_is_supermajority(sync_committee_bits));
+	});
 }
 
 #[test]
@@ -903,4 +1420,1574 @@ pub fn test_sync_committee_participation_is_supermajority_errors_when_not_superm
 
 		assert_err!(EthereumBeaconClient::sync_committee_participation_is_supermajority(sync_committee_bits), Error::<Test>::SyncCommitteeParticipantsNotSupermajority);
 	});
-}
\ No newline at end of file
+}
+#[test]
+pub fn test_sync_committee_for_slot() {
+	let update = get_finalized_header_update();
+
+	let current_sync_committee = get_current_sync_committee_for_finalized_header_update();
+
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
+	new_tester().execute_with(|| {
+		SyncCommittees::<Test>::insert(current_period, current_sync_committee.clone());
+
+		assert_eq!(
+			EthereumBeaconClient::sync_committee_for_slot(update.attested_header.slot),
+			Some(current_sync_committee)
+		);
+	});
+}
+
+#[test]
+pub fn test_sync_committee_for_slot_without_committee() {
+	let update = get_finalized_header_update();
+
+	new_tester().execute_with(|| {
+		assert_eq!(EthereumBeaconClient::sync_committee_for_slot(update.attested_header.slot), None);
+	});
+}
+
+#[test]
+pub fn test_highest_stored_period_tracks_stored_committees() {
+	new_tester().execute_with(|| {
+		assert_eq!(EthereumBeaconClient::highest_stored_period(), 0);
+
+		EthereumBeaconClient::store_sync_committee(5, SyncCommittee::default());
+		assert_eq!(EthereumBeaconClient::highest_stored_period(), 5);
+
+		EthereumBeaconClient::store_sync_committee(6, SyncCommittee::default());
+		assert_eq!(EthereumBeaconClient::highest_stored_period(), 6);
+
+		EthereumBeaconClient::store_sync_committee(4, SyncCommittee::default());
+		assert_eq!(EthereumBeaconClient::highest_stored_period(), 6);
+	});
+}
+
+#[test]
+fn it_errors_when_importing_a_header_with_an_oversized_sync_aggregate() {
+	let mut update = get_finalized_header_update();
+	update.sync_aggregate.sync_committee_bits.0.extend_from_slice(&[0xff; 64]);
+
+	new_tester().execute_with(|| {
+		assert_err!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update), Error::<Test>::InvalidSyncCommitteeBitsLength);
+	});
+}
+
+#[test]
+fn test_validate_sync_committee_bits_length() {
+	let update = get_finalized_header_update();
+
+	new_tester().execute_with(|| {
+		assert_eq!(update.sync_aggregate.sync_committee_bits.0.len(), 64);
+		assert_ok!(EthereumBeaconClient::validate_sync_aggregate(&update.sync_aggregate));
+
+		let mut empty = update.sync_aggregate.clone();
+		empty.sync_committee_bits = SyncCommitteeBits(vec![]);
+		assert_err!(EthereumBeaconClient::validate_sync_aggregate(&empty), Error::<Test>::InvalidSyncCommitteeBitsLength);
+
+		let mut short = update.sync_aggregate.clone();
+		short.sync_committee_bits.0.pop();
+		assert_err!(EthereumBeaconClient::validate_sync_aggregate(&short), Error::<Test>::InvalidSyncCommitteeBitsLength);
+	});
+}
+
+#[test]
+fn it_errors_when_importing_a_header_with_a_malformed_signature() {
+	let mut update = get_finalized_header_update();
+	update.sync_aggregate.sync_committee_signature.push(0);
+
+	new_tester().execute_with(|| {
+		assert_err!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update), Error::<Test>::InvalidSyncAggregate);
+	});
+}
+
+fn header_at(slot: u64, parent_root: H256) -> (H256, BeaconBlockHeader) {
+	let header = BeaconBlockHeader {
+		slot: Slot(slot),
+		proposer_index: 1,
+		parent_root,
+		state_root: H256::repeat_byte(slot as u8),
+		body_root: H256::repeat_byte(!(slot as u8)),
+	};
+	let block_root: H256 = merklization::hash_tree_root_beacon_header(header.clone()).unwrap().into();
+	(block_root, header)
+}
+
+#[test]
+pub fn test_store_header_with_parent_linkage() {
+	new_tester().execute_with(|| {
+		System::set_block_number(1);
+
+		// Finalized headers an epoch or more apart, whose parents are not stored.
+		let (first_root, first) = header_at(64, H256::repeat_byte(1));
+		EthereumBeaconClient::store_header(first_root, first);
+		let (second_root, second) = header_at(128, H256::repeat_byte(2));
+		EthereumBeaconClient::store_header(second_root, second);
+
+		// A header following directly on the latest one.
+		let (third_root, third) = header_at(129, second_root);
+		EthereumBeaconClient::store_header(third_root, third);
+
+		assert!(System::events().is_empty());
+	});
+}
+
+#[test]
+pub fn test_store_header_without_parent_linkage() {
+	new_tester().execute_with(|| {
+		System::set_block_number(1);
+
+		let (first_root, first) = header_at(64, H256::repeat_byte(1));
+		EthereumBeaconClient::store_header(first_root, first);
+		let (second_root, second) = header_at(128, H256::repeat_byte(2));
+		EthereumBeaconClient::store_header(second_root, second);
+
+		// Builds on the first header, skipping the latest one, so the two can't be on the
+		// same chain.
+		let (forked_root, forked) = header_at(192, first_root);
+		EthereumBeaconClient::store_header(forked_root, forked);
+		System::assert_last_event(
+			crate::Event::<Test>::HeaderChainBroken(Slot(192), forked_root).into()
+		);
+
+		// Names a parent at a later slot.
+		let (misordered_root, misordered) = header_at(96, second_root);
+		EthereumBeaconClient::store_header(misordered_root, misordered);
+		System::assert_last_event(
+			crate::Event::<Test>::HeaderChainBroken(Slot(96), misordered_root).into()
+		);
+	});
+}
+
+fn assert_scale_round_trip<T: Encode + Decode + PartialEq + sp_std::fmt::Debug>(value: T) {
+	let encoded = value.encode();
+	assert_eq!(T::decode(&mut &encoded[..]).unwrap(), value);
+}
+
+#[test]
+pub fn test_public_key_scale_round_trip() {
+	assert_scale_round_trip(PublicKey(hex!("890d863888696c371f0b815be9967e5a76e8d31327e9fe3cc93097c1b5a9a9da4ad11e9275c09f15955340db0e4bc7b6")));
+}
+
+#[test]
+pub fn test_beacon_block_header_scale_round_trip() {
+	assert_scale_round_trip(get_finalized_header_update().finalized_header);
+}
+
+#[test]
+pub fn test_sync_committee_scale_round_trip() {
+	assert_scale_round_trip(get_current_sync_committee_for_finalized_header_update());
+}
+
+#[test]
+pub fn test_sync_aggregate_scale_round_trip() {
+	assert_scale_round_trip(get_finalized_header_update().sync_aggregate);
+}
+
+#[test]
+pub fn test_initial_sync_scale_round_trip() {
+	assert_scale_round_trip(get_initial_sync());
+}
+
+#[test]
+pub fn test_sync_committee_period_update_scale_round_trip() {
+	assert_scale_round_trip(get_committee_sync_period_update());
+}
+
+#[test]
+pub fn test_finalized_header_update_scale_round_trip() {
+	assert_scale_round_trip(get_finalized_header_update());
+}
+
+#[test]
+pub fn test_fork_data_scale_round_trip() {
+	assert_scale_round_trip(ForkData {
+		current_version: hex!("70000071"),
+		genesis_validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad"),
+	});
+}
+
+#[test]
+pub fn test_signing_data_scale_round_trip() {
+	assert_scale_round_trip(SigningData {
+		object_root: hex!("63654cbe64fc07853f1198c165dd3d49c54fc53bc417989bbcc66da15f850c54").into(),
+		domain: hex!("037da907d1c3a03c0091b2254e1480d9b1783476e228ab29adaaa8f133e08f7a").into(),
+	});
+}
+
+#[test]
+pub fn test_genesis_scale_round_trip() {
+	assert_scale_round_trip(Genesis {
+		validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+	});
+}
+
+#[test]
+pub fn test_generalized_indices_change_at_electra() {
+	new_tester().execute_with(|| {
+		let pre_fork_slot = Slot(ElectraForkEpoch::get() * 32 - 1);
+		let post_fork_slot = Slot(ElectraForkEpoch::get() * 32);
+
+		let pre_fork = EthereumBeaconClient::generalized_indices(pre_fork_slot).finalized_root;
+		let post_fork = EthereumBeaconClient::generalized_indices(post_fork_slot).finalized_root;
+
+		assert_eq!((pre_fork.index, pre_fork.depth), (41, 6));
+		assert_eq!((post_fork.index, post_fork.depth), (169, 7));
+	});
+}
+
+#[test]
+pub fn test_verify_header_with_fork_aware_finalized_root_index() {
+	new_tester().execute_with(|| {
+		let leaf: H256 = hex!("1111111111111111111111111111111111111111111111111111111111111111").into();
+		let branch: Vec<H256> = (1u8..=7).map(H256::repeat_byte).collect();
+
+		let pre_fork = EthereumBeaconClient::generalized_indices(Slot(0)).finalized_root;
+		assert_ok!(EthereumBeaconClient::verify_header(
+			leaf,
+			branch[..6].to_vec(),
+			hex!("b7d3f3a3d824712161ebcd94f8e60ea7afa600fbd4e684e8e4d5adf31171a5fe").into(),
+			pre_fork.depth,
+			pre_fork.index,
+		));
+
+		let post_fork = EthereumBeaconClient::generalized_indices(Slot(ElectraForkEpoch::get() * 32)).finalized_root;
+		assert_ok!(EthereumBeaconClient::verify_header(
+			leaf,
+			branch,
+			hex!("bb8c65faf630bbd6c9606eeb38e86581132dbd4aef06b1000e5a82eef92a4864").into(),
+			post_fork.depth,
+			post_fork.index,
+		));
+	});
+}
+
+#[test]
+pub fn test_falling_behind_warning_fires_once() {
+	new_tester().execute_with(|| {
+		System::set_block_number(1);
+
+		let header = BeaconBlockHeader {
+			slot: Slot(1),
+			..Default::default()
+		};
+		let block_root: H256 = merklization::hash_tree_root_beacon_header(header.clone()).unwrap().into();
+		EthereumBeaconClient::store_header(block_root, header);
+
+		Now::set(&(FallingBehindThreshold::get() * 12));
+		EthereumBeaconClient::on_initialize(1);
+		assert!(System::events().is_empty());
+
+		Now::set(&((FallingBehindThreshold::get() + 1) * 12));
+		EthereumBeaconClient::on_initialize(2);
+		System::assert_last_event(
+			crate::Event::<Test>::FallingBehind { slots: FallingBehindThreshold::get() + 1 }.into()
+		);
+
+		Now::set(&((FallingBehindThreshold::get() + 2) * 12));
+		EthereumBeaconClient::on_initialize(3);
+		assert_eq!(System::events().len(), 1);
+	});
+}
+
+#[test]
+pub fn test_slots_behind_estimate_before_import() {
+	new_tester().execute_with(|| {
+		assert_eq!(EthereumBeaconClient::slots_behind_estimate(), None);
+	});
+}
+
+#[test]
+pub fn test_current_period() {
+	new_tester().execute_with(|| {
+		assert_eq!(EthereumBeaconClient::current_period(), None);
+
+		assert_ok!(EthereumBeaconClient::initial_sync(Origin::signed(1), get_initial_sync()));
+
+		let slot = get_initial_sync().header.slot;
+		assert_eq!(EthereumBeaconClient::latest_finalized_slot(), Some(slot));
+		assert_eq!(EthereumBeaconClient::current_period(), Some(slot.sync_period()));
+
+		// An older header does not move the current period back.
+		EthereumBeaconClient::store_header(H256::repeat_byte(1), BeaconBlockHeader { slot: Slot(1), ..Default::default() });
+		assert_eq!(EthereumBeaconClient::current_period(), Some(slot.sync_period()));
+	});
+}
+
+#[test]
+pub fn test_export_and_import_state() {
+	let snapshot = new_tester().execute_with(|| {
+		assert_eq!(EthereumBeaconClient::export_state(), None);
+
+		assert_ok!(EthereumBeaconClient::initial_sync(Origin::signed(1), get_initial_sync()));
+		let next_period = get_initial_sync().header.slot.sync_period() + 1;
+		assert_ok!(EthereumBeaconClient::force_store_sync_committee(
+			Origin::root(),
+			next_period,
+			get_initial_sync().current_sync_committee,
+		));
+
+		EthereumBeaconClient::export_state().unwrap()
+	});
+	assert_eq!(snapshot.latest_finalized_header, get_initial_sync().header);
+	assert_eq!(snapshot.genesis_validators_root, get_initial_sync().validators_root);
+	assert!(snapshot.next_sync_committee.is_some());
+
+	new_tester().execute_with(|| {
+		assert_noop!(
+			EthereumBeaconClient::import_state(Origin::signed(1), snapshot.clone()),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(EthereumBeaconClient::import_state(Origin::root(), snapshot.clone()));
+
+		assert_eq!(EthereumBeaconClient::export_state(), Some(snapshot.clone()));
+		assert_eq!(EthereumBeaconClient::header_status(snapshot.latest_finalized_header.slot), HeaderStatus::Finalized);
+	});
+}
+
+#[test]
+pub fn test_import_older_state_over_newer_state() {
+	let older = new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::initial_sync(Origin::signed(1), get_initial_sync()));
+		EthereumBeaconClient::export_state().unwrap()
+	});
+	assert_eq!(older.next_sync_committee, None);
+
+	new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::initial_sync(Origin::signed(1), get_initial_sync()));
+		let period = older.latest_finalized_header.slot.sync_period();
+
+		// Move the light client two periods past the snapshot.
+		let newer = BeaconBlockHeader {
+			slot: Slot(older.latest_finalized_header.slot.0 + 2 * crate::slots_per_period()),
+			..older.latest_finalized_header.clone()
+		};
+		let newer_root: H256 = merklization::hash_tree_root_beacon_header(newer.clone()).unwrap().into();
+		EthereumBeaconClient::store_header(newer_root, newer);
+		for newer_period in period + 1..=period + 3 {
+			EthereumBeaconClient::store_sync_committee(newer_period, older.current_sync_committee.clone());
+		}
+		let counts: Vec<u32> = (period..=period + 2).map(EthereumBeaconClient::finalized_count_in_period).collect();
+
+		assert_ok!(EthereumBeaconClient::import_state(Origin::root(), older.clone()));
+
+		assert_eq!(EthereumBeaconClient::export_state(), Some(older.clone()));
+		assert_eq!(EthereumBeaconClient::latest_finalized_slot(), Some(older.latest_finalized_header.slot));
+		assert_eq!(EthereumBeaconClient::highest_stored_period(), period);
+		assert_eq!(EthereumBeaconClient::next_expected_period(), period + 1);
+		assert_eq!(
+			(period..=period + 2).map(EthereumBeaconClient::finalized_count_in_period).collect::<Vec<u32>>(),
+			counts
+		);
+	});
+}
+
+#[test]
+pub fn test_headers_sharing_a_body_root_are_stored_independently() {
+	new_tester().execute_with(|| {
+		let first = BeaconBlockHeader {
+			slot: Slot(64),
+			proposer_index: 1,
+			parent_root: H256::repeat_byte(1),
+			state_root: H256::repeat_byte(2),
+			body_root: H256::repeat_byte(3),
+		};
+		let second = BeaconBlockHeader { slot: Slot(96), state_root: H256::repeat_byte(4), ..first.clone() };
+
+		let first_root: H256 = merklization::hash_tree_root_beacon_header(first.clone()).unwrap().into();
+		let second_root: H256 = merklization::hash_tree_root_beacon_header(second.clone()).unwrap().into();
+		assert_ne!(first_root, second_root);
+
+		EthereumBeaconClient::store_header(first_root, first.clone());
+		EthereumBeaconClient::store_header(second_root, second.clone());
+
+		assert_eq!(FinalizedHeadersBySlot::<Test>::get(first.slot), Some(first_root));
+		assert_eq!(FinalizedHeadersBySlot::<Test>::get(second.slot), Some(second_root));
+		assert_eq!(EthereumBeaconClient::verified_header(first.slot), Some(first));
+		assert_eq!(EthereumBeaconClient::verified_header(second.slot), Some(second));
+	});
+}
+
+#[test]
+pub fn test_chain_config() {
+	new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::initial_sync(Origin::signed(1), get_initial_sync()));
+
+		let config = EthereumBeaconClient::chain_config();
+		assert_eq!(config.genesis_validators_root, get_initial_sync().validators_root);
+		assert_eq!(config.slots_per_epoch, 32);
+		assert_eq!(config.epochs_per_period, 256);
+		assert_eq!(config.slots_per_epoch * config.epochs_per_period, crate::slots_per_period());
+		assert_eq!(config.electra_fork_epoch, ElectraForkEpoch::get());
+		assert_eq!(config.deneb_fork_epoch, DenebForkEpoch::get());
+		assert_eq!(
+			config.fork_versions,
+			crate::ForkVersions {
+				genesis: GenesisForkVersion::get(),
+				deneb: DenebForkVersion::get(),
+				electra: ElectraForkVersion::get(),
+			}
+		);
+	});
+}
+
+#[test]
+pub fn test_expected_current_slot() {
+	new_tester().execute_with(|| {
+		let genesis_time = GenesisTime::get();
+
+		assert_eq!(EthereumBeaconClient::expected_current_slot(genesis_time - 1), 0);
+		assert_eq!(EthereumBeaconClient::expected_current_slot(genesis_time), 0);
+		assert_eq!(EthereumBeaconClient::expected_current_slot(genesis_time + 11), 0);
+		assert_eq!(EthereumBeaconClient::expected_current_slot(genesis_time + 12), 1);
+		assert_eq!(EthereumBeaconClient::expected_current_slot(genesis_time + 29122 * 12 + 5), 29122);
+	});
+}
+
+#[test]
+pub fn test_slots_since_last_finalized() {
+	new_tester().execute_with(|| {
+		assert_eq!(EthereumBeaconClient::slots_since_last_finalized(100), None);
+
+		assert_ok!(EthereumBeaconClient::initial_sync(Origin::signed(1), get_initial_sync()));
+
+		let slot: u64 = get_initial_sync().header.slot.into();
+		assert_eq!(EthereumBeaconClient::slots_since_last_finalized(slot + 40), Some(40));
+		assert_eq!(EthereumBeaconClient::slots_since_last_finalized(slot), Some(0));
+		assert_eq!(EthereumBeaconClient::slots_since_last_finalized(slot - 1), Some(0));
+	});
+}
+
+#[test]
+pub fn test_slot_conversions() {
+	let slot = Slot::from(29122u64);
+
+	assert_eq!(u64::from(slot), 29122);
+	assert_eq!(slot.epoch(), 910);
+	assert_eq!(slot.sync_period(), 3);
+	assert_eq!(slot.encode(), 29122u64.encode());
+	assert_eq!(Slot::decode(&mut &29122u64.encode()[..]).unwrap(), slot);
+}
+
+#[test]
+pub fn test_set_genesis_validators_root() {
+	new_tester().execute_with(|| {
+		System::set_block_number(1);
+
+		let validators_root: H256 = hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into();
+
+		assert_ok!(EthereumBeaconClient::set_genesis_validators_root(Origin::root(), validators_root));
+
+		assert_eq!(ChainGenesis::<Test>::get().validators_root, validators_root);
+		System::assert_last_event(crate::Event::<Test>::GenesisRootUpdated(validators_root).into());
+
+		let domain = EthereumBeaconClient::compute_domain(
+			hex!("07000000").into(),
+			hex!("70000071").into(),
+			ChainGenesis::<Test>::get().validators_root,
+		);
+		assert_eq!(
+			domain.unwrap(),
+			hex!("07000000e7acb21061790987fa1c1e745cccfb358370b33e8af2b2c18938e6c2").into()
+		);
+	});
+}
+
+#[test]
+pub fn test_set_genesis_validators_root_requires_admin() {
+	new_tester().execute_with(|| {
+		assert_noop!(
+			EthereumBeaconClient::set_genesis_validators_root(Origin::signed(1), H256::repeat_byte(1)),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+pub fn test_optimistic_header_update() {
+	new_tester().execute_with(|| {
+		let validators_root: H256 = hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into();
+		let fork_version = hex!("70000071");
+		let header = BeaconBlockHeader {
+			slot: Slot(29122),
+			proposer_index: 3,
+			parent_root: H256::repeat_byte(1),
+			state_root: H256::repeat_byte(2),
+			body_root: H256::repeat_byte(3),
+		};
+		let sync_aggregate = SyncAggregate {
+			sync_committee_bits: SyncCommitteeBits(vec![0xff; SYNC_COMMITTEE_SIZE / 8]),
+			sync_committee_signature: minimal_committee::sign(header.clone(), &[1, 1, 1, 1], fork_version, validators_root),
+		};
+
+		ChainGenesis::<Test>::set(Genesis { validators_root });
+		SyncCommittees::<Test>::insert(
+			header.slot.sync_period(),
+			SyncCommittee { pubkeys: minimal_committee::pubkeys(), aggregate_pubkey: PublicKey([0; 48]) },
+		);
+
+		assert_ok!(EthereumBeaconClient::optimistic_header_update(
+			Origin::signed(1),
+			header.clone(),
+			sync_aggregate.clone(),
+			fork_version,
+		));
+
+		assert_eq!(EthereumBeaconClient::optimistic_header(), Some(header.clone()));
+		assert_eq!(EthereumBeaconClient::verified_header(header.slot), None);
+		assert_eq!(EthereumBeaconClient::header_status(header.slot), HeaderStatus::Unknown);
+
+		assert_noop!(
+			EthereumBeaconClient::optimistic_header_update(Origin::signed(1), header, sync_aggregate, fork_version),
+			Error::<Test>::StaleOptimisticHeader
+		);
+	});
+}
+
+#[test]
+pub fn test_force_store_sync_committee() {
+	let sync_committee = get_initial_sync().current_sync_committee;
+
+	new_tester().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			EthereumBeaconClient::force_store_sync_committee(Origin::signed(1), 7, sync_committee.clone()),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(EthereumBeaconClient::force_store_sync_committee(Origin::root(), 7, sync_committee.clone()));
+
+		assert_eq!(SyncCommittees::<Test>::get(7), sync_committee);
+		assert_eq!(EthereumBeaconClient::next_expected_period(), 8);
+		System::assert_last_event(crate::Event::<Test>::ForcedCommitteeStored(7).into());
+	});
+}
+
+#[test]
+pub fn test_verify_header_signed_by_minimal_committee() {
+	new_tester().execute_with(|| {
+		let validators_root: H256 = hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into();
+		let fork_version = hex!("70000071");
+		let header = BeaconBlockHeader {
+			slot: Slot(29122),
+			proposer_index: 3,
+			parent_root: H256::repeat_byte(1),
+			state_root: H256::repeat_byte(2),
+			body_root: H256::repeat_byte(3),
+		};
+		let participation = vec![1, 1, 0, 1];
+		let signature = minimal_committee::sign(header.clone(), &participation, fork_version, validators_root);
+
+		assert_ok!(EthereumBeaconClient::verify_signed_header(
+			participation,
+			signature.clone(),
+			minimal_committee::pubkeys(),
+			fork_version,
+			header.clone(),
+			validators_root,
+		));
+
+		// A signature claiming a different set of participants must not verify.
+		assert_err!(
+			EthereumBeaconClient::verify_signed_header(
+				vec![1, 1, 1, 1],
+				signature,
+				minimal_committee::pubkeys(),
+				fork_version,
+				header,
+				validators_root,
+			),
+			Error::<Test>::SignatureVerificationFailed
+		);
+	});
+}
+
+#[test]
+pub fn test_participation_rate() {
+	let update = get_finalized_header_update();
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
+	new_tester().execute_with(|| {
+		assert_eq!(EthereumBeaconClient::participation_rate(current_period), None);
+
+		SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_finalized_header_update());
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+
+		assert_ok!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()));
+
+		let participants = EthereumBeaconClient::get_sync_committee_sum(
+			update.sync_aggregate.sync_committee_bits.iter_bits().collect()
+		).unwrap();
+		assert_eq!(
+			ParticipationStatsByPeriod::<Test>::get(current_period),
+			ParticipationStats { headers: 1, participants }
+		);
+	});
+
+	new_tester().execute_with(|| {
+		EthereumBeaconClient::record_participation(5, 512);
+		EthereumBeaconClient::record_participation(5, 384);
+		EthereumBeaconClient::record_participation(5, 384);
+		EthereumBeaconClient::record_participation(5, 256);
+
+		// (512 + 384 + 384 + 256) / (4 * 512) = 0.75
+		assert_eq!(EthereumBeaconClient::participation_rate(5), Some(Perbill::from_percent(75)));
+		assert_eq!(EthereumBeaconClient::participation_rate(6), None);
+	});
+}
+
+#[test]
+pub fn test_import_finalized_header_already_imported() {
+	let update = get_finalized_header_update();
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
+	new_tester().execute_with(|| {
+		SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_finalized_header_update());
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+
+		assert_ok!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()));
+		assert_err!(
+			EthereumBeaconClient::import_finalized_header(Origin::signed(1), update),
+			Error::<Test>::AlreadyImported
+		);
+	});
+}
+
+#[test]
+pub fn test_import_finalized_header_epoch_alignment() {
+	let update = get_finalized_header_update();
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
+	new_tester().execute_with(|| {
+		RequireEpochAlignedFinalizedSlot::set(&true);
+		SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_finalized_header_update());
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+
+		let mut unaligned = update.clone();
+		unaligned.finalized_header.slot = Slot(update.finalized_header.slot.0 + 1);
+		assert_err!(
+			EthereumBeaconClient::import_finalized_header(Origin::signed(1), unaligned),
+			Error::<Test>::UnalignedFinalizedSlot
+		);
+
+		assert_eq!(update.finalized_header.slot.0 % 32, 0);
+		assert_ok!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update));
+	});
+}
+
+#[test]
+pub fn test_import_finalized_header_conflicting_with_stored_header() {
+	let update = get_finalized_header_update();
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+	let slot = update.finalized_header.slot;
+	let stored_root = H256::repeat_byte(1);
+
+	new_tester().execute_with(|| {
+		System::set_block_number(1);
+		SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_finalized_header_update());
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+		<FinalizedHeadersBySlot<Test>>::insert(slot, stored_root);
+
+		assert_err!(
+			EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()),
+			Error::<Test>::FinalizedHeaderConflict
+		);
+
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == crate::Event::<Test>::FinalizedHeaderConflict { slot }.into()));
+		System::assert_last_event(
+			crate::Event::<Test>::FinalizedHeaderImportFailed { slot, retryable: false }.into()
+		);
+		assert_eq!(MockRelayerRewards::rewarded(), vec![]);
+		let block_root: H256 = merklization::hash_tree_root_beacon_header(update.finalized_header).unwrap().into();
+		assert_eq!(<FinalizedHeadersBySlot<Test>>::get(slot), Some(stored_root));
+		assert!(!<FinalizedHeaders<Test>>::contains_key(block_root));
+	});
+}
+
+#[test]
+pub fn test_public_key_from_bytes() {
+	let pubkey = get_initial_sync().current_sync_committee.pubkeys[0].clone();
+	let bytes = pubkey.encode();
+
+	assert_eq!(PublicKey::from_bytes(&bytes), Ok(pubkey.clone()));
+	assert_eq!(PublicKey::from_hex(&sp_core::bytes::to_hex(&bytes, false)), Ok(pubkey));
+}
+
+#[test]
+pub fn test_public_key_from_bytes_wrong_length() {
+	assert_eq!(PublicKey::from_bytes(&[0u8; 47]), Err(PublicKeyError::InvalidLength));
+	assert_eq!(PublicKey::from_bytes(&[0u8; 49]), Err(PublicKeyError::InvalidLength));
+	assert_eq!(PublicKey::from_hex("0xzz"), Err(PublicKeyError::InvalidHex));
+}
+
+#[test]
+pub fn test_public_key_from_bytes_invalid_point() {
+	assert_eq!(PublicKey::from_bytes(&[0xff; 48]), Err(PublicKeyError::InvalidPoint));
+}
+
+#[test]
+pub fn test_repeated_signature_verification_is_cached() {
+	new_tester().execute_with(|| {
+		let validators_root: H256 = hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into();
+		let fork_version = hex!("70000071");
+		let header = BeaconBlockHeader {
+			slot: Slot(29122),
+			proposer_index: 3,
+			parent_root: H256::repeat_byte(1),
+			state_root: H256::repeat_byte(2),
+			body_root: H256::repeat_byte(3),
+		};
+		let participation = vec![1, 1, 1, 1];
+		let signature = minimal_committee::sign(header.clone(), &participation, fork_version, validators_root);
+		let verify = |bits: Vec<u8>| EthereumBeaconClient::verify_signed_header(
+			bits,
+			signature.clone(),
+			minimal_committee::pubkeys(),
+			fork_version,
+			header.clone(),
+			validators_root,
+		);
+
+		let before = metrics::verification_counts().attempted;
+		assert_ok!(verify(participation.clone()));
+		assert_ok!(verify(participation));
+		assert_eq!(metrics::verification_counts().attempted, before + 1);
+
+		// The same signature claimed by a different participant set is verified afresh.
+		assert_err!(verify(vec![1, 1, 1, 0]), Error::<Test>::SignatureVerificationFailed);
+		assert_eq!(metrics::verification_counts().attempted, before + 2);
+	});
+}
+
+#[test]
+pub fn test_verify_header_rejects_all_zero_branch() {
+	new_tester().execute_with(|| {
+		let gindex = EthereumBeaconClient::generalized_indices(Slot(1)).finalized_root;
+
+		assert_err!(
+			EthereumBeaconClient::verify_header(
+				H256::repeat_byte(1),
+				vec![H256::zero(); gindex.depth as usize],
+				H256::repeat_byte(2),
+				gindex.depth,
+				gindex.index,
+			),
+			Error::<Test>::MalformedProofBranch
+		);
+
+		let mut branch = vec![H256::zero(); gindex.depth as usize];
+		branch[0] = H256::repeat_byte(3);
+		assert_err!(
+			EthereumBeaconClient::verify_header(
+				H256::repeat_byte(1),
+				branch,
+				H256::repeat_byte(2),
+				gindex.depth,
+				gindex.index,
+			),
+			Error::<Test>::InvalidHeaderMerkleProof
+		);
+	});
+}
+
+#[test]
+pub fn test_verify_header_rejects_overlong_branch() {
+	new_tester().execute_with(|| {
+		let max = MaxProofBranchLength::get() as u64;
+
+		assert_err!(
+			EthereumBeaconClient::verify_header(
+				H256::repeat_byte(1),
+				vec![H256::repeat_byte(3); max as usize],
+				H256::repeat_byte(2),
+				max,
+				1,
+			),
+			Error::<Test>::InvalidHeaderMerkleProof
+		);
+
+		assert_err!(
+			EthereumBeaconClient::verify_header(
+				H256::repeat_byte(1),
+				vec![H256::repeat_byte(3); max as usize + 1],
+				H256::repeat_byte(2),
+				max + 1,
+				1,
+			),
+			Error::<Test>::ProofBranchTooLong
+		);
+	});
+}
+
+#[test]
+pub fn test_validate_proposer_index() {
+	new_tester().execute_with(|| {
+		let header = BeaconBlockHeader { proposer_index: MaxProposerIndex::get(), ..Default::default() };
+		assert_ok!(EthereumBeaconClient::validate_proposer_index(&header));
+
+		let header = BeaconBlockHeader { proposer_index: MaxProposerIndex::get() + 1, ..Default::default() };
+		assert_err!(EthereumBeaconClient::validate_proposer_index(&header), Error::<Test>::InvalidProposerIndex);
+	});
+}
+
+#[test]
+pub fn test_finalized_header_with_invalid_proposer_index_rejected() {
+	let mut update = get_finalized_header_update();
+	update.attested_header.proposer_index = MaxProposerIndex::get() + 1;
+
+	new_tester().execute_with(|| {
+		assert_err!(
+			EthereumBeaconClient::import_finalized_header(Origin::signed(1), update),
+			Error::<Test>::InvalidProposerIndex
+		);
+	});
+}
+
+#[test]
+pub fn test_header_submission_rejected_for_disallowed_origin() {
+	let update = get_finalized_header_update();
+
+	new_tester().execute_with(|| {
+		AllowedRelayer::set(&Some(1));
+
+		assert_noop!(
+			EthereumBeaconClient::import_finalized_header(Origin::signed(2), update.clone()),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			EthereumBeaconClient::import_finalized_header(Origin::none(), update),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+pub fn test_header_status() {
+	let update = get_finalized_header_update();
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
+	new_tester().execute_with(|| {
+		SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_finalized_header_update());
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+
+		assert_eq!(EthereumBeaconClient::header_status(update.finalized_header.slot), HeaderStatus::Unknown);
+
+		assert_ok!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()));
+
+		assert_eq!(EthereumBeaconClient::header_status(update.finalized_header.slot), HeaderStatus::Finalized);
+		assert_eq!(EthereumBeaconClient::header_status(update.attested_header.slot), HeaderStatus::Unknown);
+	});
+}
+
+#[test]
+pub fn test_verified_header() {
+	let update = get_finalized_header_update();
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
+	new_tester().execute_with(|| {
+		SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_finalized_header_update());
+		ChainGenesis::<Test>::set(Genesis{
+			validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+		});
+
+		let mut unsigned = update.clone();
+		unsigned.sync_aggregate.sync_committee_bits = SyncCommitteeBits(vec![0; unsigned.sync_aggregate.sync_committee_bits.0.len()]);
+		assert!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), unsigned).is_err());
+		assert_eq!(EthereumBeaconClient::verified_header(update.finalized_header.slot), None);
+
+		assert_ok!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()));
+
+		assert_eq!(EthereumBeaconClient::verified_header(update.finalized_header.slot), Some(update.finalized_header.clone()));
+		assert_eq!(EthereumBeaconClient::verified_header(update.attested_header.slot), None);
+	});
+}
+
+#[test]
+pub fn test_verification_counts() {
+	new_tester().execute_with(|| {
+		let validators_root = H256::repeat_byte(9);
+		let fork_version = hex!("70000071");
+		let header = BeaconBlockHeader {
+			slot: Slot(1),
+			..Default::default()
+		};
+		let participation = vec![1, 0, 1, 1];
+		let signature = minimal_committee::sign(header.clone(), &participation, fork_version, validators_root);
+		let before = metrics::verification_counts();
+
+		assert_ok!(EthereumBeaconClient::verify_signed_header(
+			participation,
+			signature.clone(),
+			minimal_committee::pubkeys(),
+			fork_version,
+			header.clone(),
+			validators_root,
+		));
+		assert_err!(
+			EthereumBeaconClient::verify_signed_header(
+				vec![1, 1, 1, 1],
+				signature,
+				minimal_committee::pubkeys(),
+				fork_version,
+				header,
+				validators_root,
+			),
+			Error::<Test>::SignatureVerificationFailed
+		);
+
+		let after = metrics::verification_counts();
+		assert_eq!(after.attempted, before.attempted + 2);
+		assert_eq!(after.succeeded, before.succeeded + 1);
+		assert_eq!(after.failed, before.failed + 1);
+	});
+}
+
+#[test]
+pub fn test_import_finalized_header_zero_state_root() {
+	let mut update = get_finalized_header_update();
+	update.finalized_header.state_root = H256::zero();
+
+	new_tester().execute_with(|| {
+		assert_err!(
+			EthereumBeaconClient::import_finalized_header(Origin::signed(1), update),
+			Error::<Test>::DegenerateHeader
+		);
+	});
+}
+
+#[test]
+pub fn test_import_finalized_header_zero_body_root() {
+	let mut update = get_finalized_header_update();
+	update.finalized_header.body_root = H256::zero();
+
+	new_tester().execute_with(|| {
+		assert_err!(
+			EthereumBeaconClient::import_finalized_header(Origin::signed(1), update),
+			Error::<Test>::DegenerateHeader
+		);
+	});
+}
+
+#[test]
+pub fn test_validate_sync_committee_size() {
+	new_tester().execute_with(|| {
+		let mut sync_committee = get_initial_sync().current_sync_committee;
+		assert_eq!(sync_committee.pubkeys.len(), SYNC_COMMITTEE_SIZE);
+		assert_ok!(EthereumBeaconClient::validate_sync_committee(&sync_committee));
+
+		let extra = sync_committee.pubkeys[0].clone();
+		sync_committee.pubkeys.push(extra);
+		assert_err!(
+			EthereumBeaconClient::validate_sync_committee(&sync_committee),
+			Error::<Test>::InvalidSyncCommitteeSize
+		);
+
+		sync_committee.pubkeys.truncate(SYNC_COMMITTEE_SIZE - 1);
+		assert_err!(
+			EthereumBeaconClient::validate_sync_committee(&sync_committee),
+			Error::<Test>::InvalidSyncCommitteeSize
+		);
+	});
+}
+
+#[test]
+pub fn test_validate_sync_committee_aggregate_pubkey() {
+	new_tester().execute_with(|| {
+		let mut sync_committee = get_initial_sync().current_sync_committee;
+		assert_ok!(EthereumBeaconClient::validate_sync_committee(&sync_committee));
+
+		sync_committee.aggregate_pubkey = PublicKey([0; 48]);
+		assert_err!(
+			EthereumBeaconClient::validate_sync_committee(&sync_committee),
+			Error::<Test>::InvalidAggregatePublicKeys
+		);
+
+		let mut infinity = [0; 48];
+		infinity[0] = 0xc0;
+		sync_committee.aggregate_pubkey = PublicKey(infinity);
+		assert_err!(
+			EthereumBeaconClient::force_store_sync_committee(Origin::root(), 7, sync_committee),
+			Error::<Test>::InvalidAggregatePublicKeys
+		);
+		assert_eq!(SyncCommittees::<Test>::try_get(7), Err(()));
+	});
+}
+
+#[test]
+pub fn test_next_expected_period() {
+	new_tester().execute_with(|| {
+		EthereumBeaconClient::store_sync_committee(5, SyncCommittee::default());
+		assert_eq!(EthereumBeaconClient::next_expected_period(), 6);
+
+		EthereumBeaconClient::store_sync_committee(6, SyncCommittee::default());
+		assert_eq!(EthereumBeaconClient::next_expected_period(), 7);
+	});
+}
+
+#[test]
+pub fn test_sync_committee_root() {
+	new_tester().execute_with(|| {
+		let initial_sync = get_initial_sync();
+
+		// Root of the Prater sync committee proven in the initial sync header's state.
+		let root = EthereumBeaconClient::sync_committee_root(&initial_sync.current_sync_committee).unwrap();
+		assert_eq!(root, hex!("adb6aa3a8cb9da8bef033b4a85a54df4f37f145545bc436a0726537204ff227a").into());
+
+		let gindex = EthereumBeaconClient::generalized_indices(initial_sync.header.slot).current_sync_committee;
+		assert!(EthereumBeaconClient::is_valid_merkle_branch(
+			root,
+			initial_sync.current_sync_committee_branch,
+			gindex.depth,
+			gindex.index,
+			initial_sync.header.state_root,
+		));
+	});
+}
+
+#[test]
+pub fn test_sync_committee_root_oversized_committee() {
+	new_tester().execute_with(|| {
+		let mut sync_committee = get_initial_sync().current_sync_committee;
+		let extra = sync_committee.pubkeys[0].clone();
+		sync_committee.pubkeys.push(extra);
+
+		assert_err!(
+			EthereumBeaconClient::sync_committee_root(&sync_committee),
+			Error::<Test>::InvalidHash
+		);
+	});
+}
+
+#[test]
+pub fn test_bls_fast_aggregate_verify_is_order_independent() {
+	new_tester().execute_with(|| {
+		let validators_root = H256::repeat_byte(9);
+		let fork_version = hex!("70000071");
+		let header = BeaconBlockHeader {
+			slot: Slot(1),
+			..Default::default()
+		};
+		let signature = minimal_committee::sign(header.clone(), &[1, 1, 1, 1], fork_version, validators_root);
+		let domain = EthereumBeaconClient::compute_domain(hex!("07000000").into(), Some(fork_version), validators_root).unwrap();
+		let signing_root = EthereumBeaconClient::compute_signing_root(header, domain).unwrap();
+
+		let pubkeys = minimal_committee::pubkeys();
+		let mut reversed = pubkeys.clone();
+		reversed.reverse();
+		let shuffled = vec![pubkeys[2].clone(), pubkeys[0].clone(), pubkeys[3].clone(), pubkeys[1].clone()];
+
+		assert_ok!(EthereumBeaconClient::bls_fast_aggregate_verify(pubkeys, signing_root, signature.clone()));
+		assert_ok!(EthereumBeaconClient::bls_fast_aggregate_verify(reversed, signing_root, signature.clone()));
+		assert_ok!(EthereumBeaconClient::bls_fast_aggregate_verify(shuffled, signing_root, signature));
+	});
+}
+
+#[test]
+pub fn test_period_boundaries() {
+	assert_eq!(crate::slots_per_period(), 8192);
+
+	assert_eq!(crate::period_start_slot(0), Slot(0));
+	assert_eq!(crate::period_end_slot(0), Slot(8191));
+
+	assert_eq!(crate::period_start_slot(3), Slot(24576));
+	assert_eq!(crate::period_end_slot(3), Slot(32767));
+
+	assert_eq!(crate::period_start_slot(3).sync_period(), 3);
+	assert_eq!(crate::period_end_slot(3).sync_period(), 3);
+	assert_eq!(Slot(crate::period_end_slot(3).0 + 1).sync_period(), 4);
+}
+
+#[test]
+pub fn test_period_context() {
+	let context = crate::period_context(Slot(12000));
+	assert_eq!(context.period, 1);
+	assert_eq!(context.start_slot, Slot(8192));
+	assert_eq!(context.end_slot, Slot(16383));
+	assert_eq!(context.adjacent_period, 2);
+
+	let context = crate::period_context(Slot(16383));
+	assert_eq!(context.period, 1);
+	assert_eq!(context.end_slot, Slot(16383));
+	assert_eq!(context.adjacent_period, 2);
+
+	let context = crate::period_context(Slot(16384));
+	assert_eq!(context.period, 2);
+	assert_eq!(context.start_slot, Slot(16384));
+	assert_eq!(context.adjacent_period, 3);
+}
+
+fn merkle_root(leaf: H256, branch: &[H256], index: u64) -> H256 {
+	branch.iter().enumerate().fold(leaf, |node, (i, sibling)| {
+		let mut pair = [0u8; 64];
+		if index.checked_shr(i as u32).unwrap_or(0) & 1 == 1 {
+			pair[..32].copy_from_slice(sibling.as_bytes());
+			pair[32..].copy_from_slice(node.as_bytes());
+		} else {
+			pair[..32].copy_from_slice(node.as_bytes());
+			pair[32..].copy_from_slice(sibling.as_bytes());
+		}
+		sp_io::hashing::sha2_256(&pair).into()
+	})
+}
+
+#[test]
+pub fn test_verify_execution_proof() {
+	let message = ropsten_london_message();
+	let execution_header = ropsten_london_header();
+	let execution_branch: Vec<H256> = (1..=8).map(H256::repeat_byte).collect();
+	let beacon_proof = BeaconProof { slot: 64, execution_header, execution_branch };
+
+	new_tester().execute_with(|| {
+		assert_err!(
+			EthereumBeaconClient::verify_execution_proof(&message, &beacon_proof),
+			Error::<Test>::MissingHeader
+		);
+
+		let beacon_header = BeaconBlockHeader {
+			slot: Slot(64),
+			proposer_index: 1,
+			parent_root: H256::repeat_byte(11),
+			state_root: H256::repeat_byte(12),
+			body_root: merkle_root(message.proof.block_hash, &beacon_proof.execution_branch, 412),
+		};
+		let block_root: H256 = merklization::hash_tree_root_beacon_header(beacon_header.clone()).unwrap().into();
+		EthereumBeaconClient::store_header(block_root, beacon_header);
+
+		assert_ok!(EthereumBeaconClient::verify_execution_proof(&message, &beacon_proof));
+
+		let mut wrong_branch = beacon_proof.clone();
+		wrong_branch.execution_branch[0] = H256::repeat_byte(9);
+		assert_err!(
+			EthereumBeaconClient::verify_execution_proof(&message, &wrong_branch),
+			Error::<Test>::InvalidProof
+		);
+
+		let mut wrong_block = message.clone();
+		wrong_block.proof.block_hash = H256::repeat_byte(9);
+		assert_err!(
+			EthereumBeaconClient::verify_execution_proof(&wrong_block, &beacon_proof),
+			Error::<Test>::InvalidProof
+		);
+	});
+}
+
+#[test]
+pub fn test_verify_execution_proof_after_deneb() {
+	let message = ropsten_london_message();
+	let execution_header = ropsten_london_header();
+	let slot = DenebForkEpoch::get() * 32;
+	let execution_branch: Vec<H256> = (1..=9).map(H256::repeat_byte).collect();
+	let beacon_proof = BeaconProof { slot, execution_header, execution_branch };
+
+	new_tester().execute_with(|| {
+		let beacon_header = BeaconBlockHeader {
+			slot: Slot(slot),
+			proposer_index: 1,
+			parent_root: H256::repeat_byte(11),
+			state_root: H256::repeat_byte(12),
+			body_root: merkle_root(message.proof.block_hash, &beacon_proof.execution_branch, 812),
+		};
+		let block_root: H256 = merklization::hash_tree_root_beacon_header(beacon_header.clone()).unwrap().into();
+		EthereumBeaconClient::store_header(block_root, beacon_header);
+
+		assert_ok!(EthereumBeaconClient::verify_execution_proof(&message, &beacon_proof));
+
+		// A pre-Deneb proof for the same block doesn't verify once Deneb is active.
+		let mut pre_deneb = beacon_proof.clone();
+		pre_deneb.execution_branch.pop();
+		assert_err!(
+			EthereumBeaconClient::verify_execution_proof(&message, &pre_deneb),
+			Error::<Test>::InvalidProof
+		);
+	});
+}
+
+#[test]
+pub fn test_basic_channel_submit_with_beacon_proof() {
+	use snowbridge_basic_channel::inbound::{Error as ChannelError, SourceChannel};
+	use snowbridge_core::{ChannelId, MessageId};
+
+	let message = ropsten_london_message();
+	let execution_header = ropsten_london_header();
+	let execution_branch: Vec<H256> = (1..=8).map(H256::repeat_byte).collect();
+	let beacon_proof = BeaconProof { slot: 64, execution_header, execution_branch };
+
+	new_tester().execute_with(|| {
+		SourceChannel::<Test>::put(H160(hex!("5dd2b8d6f10623426b74d7a92d322f75b74571a3")));
+
+		// Nothing is dispatched until the beacon header committing to the block is finalized.
+		assert_err!(
+			BasicInboundChannel::submit_with_beacon_proof(Origin::signed(1), message.clone(), beacon_proof.clone()),
+			Error::<Test>::MissingHeader
+		);
+
+		let beacon_header = BeaconBlockHeader {
+			slot: Slot(64),
+			proposer_index: 1,
+			parent_root: H256::repeat_byte(11),
+			state_root: H256::repeat_byte(12),
+			body_root: merkle_root(message.proof.block_hash, &beacon_proof.execution_branch, 412),
+		};
+		let block_root: H256 = merklization::hash_tree_root_beacon_header(beacon_header.clone()).unwrap().into();
+		EthereumBeaconClient::store_header(block_root, beacon_header);
+
+		let mut wrong_branch = beacon_proof.clone();
+		wrong_branch.execution_branch[0] = H256::repeat_byte(9);
+		assert_err!(
+			BasicInboundChannel::submit_with_beacon_proof(Origin::signed(1), message.clone(), wrong_branch),
+			Error::<Test>::InvalidProof
+		);
+
+		assert_ok!(BasicInboundChannel::submit_with_beacon_proof(
+			Origin::signed(1),
+			message.clone(),
+			beacon_proof.clone()
+		));
+		assert_eq!(BasicInboundChannel::highest_processed_nonce(), 1);
+		assert_eq!(
+			MockMessageDispatch::dispatched(),
+			vec![(
+				H160(hex!("00273e201ffb0bccce44560454fb6841429d5071")),
+				MessageId::new(ChannelId::Basic, 1),
+				hex!("4101ef42cf85be6adf3081ada73af87e27996046fe6300d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d000014bbf08ac602000000000000000000000000000000000000000000000000").to_vec(),
+			)]
+		);
+
+		assert_err!(
+			BasicInboundChannel::submit_with_beacon_proof(Origin::signed(1), message, beacon_proof),
+			ChannelError::<Test>::AlreadyProcessed
+		);
+	});
+}
+
+#[test]
+pub fn test_execution_block_hash_index_by_fork() {
+	let pre_fork = EthereumBeaconClient::execution_block_hash_index(Slot(DenebForkEpoch::get() * 32 - 1));
+	assert_eq!((pre_fork.index, pre_fork.depth), (412, 8));
+
+	let post_fork = EthereumBeaconClient::execution_block_hash_index(Slot(DenebForkEpoch::get() * 32));
+	assert_eq!((post_fork.index, post_fork.depth), (812, 9));
+}
+
+/// Deterministic xorshift generator, so that failing cases are reproducible.
+struct Rng(u64);
+
+impl Rng {
+	fn next(&mut self) -> u64 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		self.0
+	}
+
+	fn below(&mut self, bound: u64) -> u64 {
+		self.next() % bound
+	}
+
+	fn hash(&mut self) -> H256 {
+		let mut bytes = [0u8; 32];
+		for chunk in bytes.chunks_mut(8) {
+			chunk.copy_from_slice(&self.next().to_le_bytes());
+		}
+		bytes.into()
+	}
+}
+
+#[test]
+pub fn test_is_valid_merkle_branch_properties() {
+	let mut rng = Rng(0x5eed_cafe_f00d_beef);
+
+	new_tester().execute_with(|| {
+		for _ in 0..200 {
+			let depth = rng.below(70);
+			let index = if depth >= 64 { rng.next() } else { rng.below(1 << depth) };
+			let leaf = rng.hash();
+			let branch: Vec<H256> = (0..depth).map(|_| rng.hash()).collect();
+			let root = merkle_root(leaf, &branch, index);
+
+			assert!(EthereumBeaconClient::is_valid_merkle_branch(leaf, branch.clone(), depth, index, root));
+
+			if depth > 0 {
+				let mut corrupted = branch.clone();
+				corrupted[rng.below(depth) as usize] = rng.hash();
+				assert!(!EthereumBeaconClient::is_valid_merkle_branch(leaf, corrupted, depth, index, root));
+
+				assert!(!EthereumBeaconClient::is_valid_merkle_branch(rng.hash(), branch.clone(), depth, index, root));
+			}
+
+			// Arbitrary inputs must be rejected without panicking.
+			let garbage: Vec<H256> = (0..rng.below(70)).map(|_| rng.hash()).collect();
+			EthereumBeaconClient::is_valid_merkle_branch(leaf, garbage, rng.next(), rng.next(), root);
+			assert!(!EthereumBeaconClient::is_valid_merkle_branch(leaf, branch, depth + 1, index, root));
+		}
+	});
+}
+
+#[test]
+pub fn test_migrate_to_v1() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_tester().execute_with(|| {
+		StorageVersion::new(0).put::<EthereumBeaconClient>();
+		for slot in [64, 8256, 4096, 128] {
+			FinalizedHeadersBySlot::<Test>::insert(Slot(slot), H256::repeat_byte(slot as u8));
+		}
+		for period in [2, 0, 1] {
+			SyncCommittees::<Test>::insert(period, SyncCommittee::default());
+		}
+
+		assert_ok!(crate::migration::v1::pre_migrate::<Test>());
+		<EthereumBeaconClient as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_ok!(crate::migration::v1::post_migrate::<Test>());
+
+		assert_eq!(EthereumBeaconClient::on_chain_storage_version(), 1);
+		assert_eq!(EthereumBeaconClient::latest_finalized_slot(), Some(Slot(8256)));
+		assert_eq!(EthereumBeaconClient::highest_stored_period(), 2);
+		assert_eq!(EthereumBeaconClient::next_expected_period(), 3);
+		assert_eq!(EthereumBeaconClient::finalized_count_in_period(0), 3);
+		assert_eq!(EthereumBeaconClient::finalized_count_in_period(1), 1);
+
+		// Running again is a no-op.
+		FinalizedHeadersBySlot::<Test>::insert(Slot(16448), H256::repeat_byte(1));
+		<EthereumBeaconClient as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(EthereumBeaconClient::latest_finalized_slot(), Some(Slot(8256)));
+		assert_eq!(EthereumBeaconClient::finalized_count_in_period(2), 0);
+	});
+}
+
+#[test]
+pub fn test_bls_verification_goes_through_configured_backend() {
+	new_tester().execute_with(|| {
+		AcceptAllSignatures::set(&true);
+
+		let pubkeys = vec![PublicKey([1; 48]), PublicKey([2; 48])];
+		let message = H256::repeat_byte(3);
+		let signature = vec![4; 96];
+
+		let before = MockBls::calls().len();
+		assert_ok!(EthereumBeaconClient::bls_fast_aggregate_verify(pubkeys.clone(), message, signature.clone()));
+
+		let calls = MockBls::calls();
+		assert_eq!(calls.len(), before + 1);
+		assert_eq!(calls.last(), Some(&(pubkeys, message, signature)));
+	});
+}
+
+#[test]
+pub fn test_verify_signed_headers_bracketing_a_fork_activation() {
+	new_tester().execute_with(|| {
+		let validators_root = H256::repeat_byte(9);
+		let (pre_fork_version, post_fork_version) = (hex!("02000000"), hex!("03000000"));
+		let activation_slot = 10 * 32;
+		let participation = vec![1, 1, 1, 1];
+
+		let last_pre_fork = BeaconBlockHeader { slot: Slot(activation_slot - 1), ..Default::default() };
+		let first_post_fork = BeaconBlockHeader { slot: Slot(activation_slot), ..Default::default() };
+
+		let verify = |header: &BeaconBlockHeader, signed_with, verified_with| {
+			EthereumBeaconClient::verify_signed_header(
+				participation.clone(),
+				minimal_committee::sign(header.clone(), &participation, signed_with, validators_root),
+				minimal_committee::pubkeys(),
+				verified_with,
+				header.clone(),
+				validators_root,
+			)
+		};
+
+		assert_ok!(verify(&last_pre_fork, pre_fork_version, pre_fork_version));
+		assert_ok!(verify(&first_post_fork, post_fork_version, post_fork_version));
+
+		// Each header only verifies under the domain of the fork it was signed in.
+		assert_err!(verify(&last_pre_fork, pre_fork_version, post_fork_version), Error::<Test>::SignatureVerificationFailed);
+		assert_err!(verify(&first_post_fork, post_fork_version, pre_fork_version), Error::<Test>::SignatureVerificationFailed);
+	});
+}
+
+#[test]
+pub fn test_verify_signed_header_rejects_no_participants() {
+	new_tester().execute_with(|| {
+		AcceptAllSignatures::set(&true);
+
+		let validators_root = H256::repeat_byte(9);
+		let fork_version = hex!("70000071");
+		let header = BeaconBlockHeader { slot: Slot(1), ..Default::default() };
+		let signature = minimal_committee::sign(header.clone(), &[1, 1, 1, 1], fork_version, validators_root);
+
+		let before = MockBls::calls().len();
+		assert_err!(
+			EthereumBeaconClient::verify_signed_header(
+				vec![0, 0, 0, 0],
+				signature,
+				minimal_committee::pubkeys(),
+				fork_version,
+				header,
+				validators_root,
+			),
+			Error::<Test>::InsufficientSyncCommitteeParticipants
+		);
+		assert_eq!(MockBls::calls().len(), before);
+	});
+}
+
+#[test]
+pub fn test_check_sync_committee_proof() {
+	new_tester().execute_with(|| {
+		let initial_sync = get_initial_sync();
+		let gindex = EthereumBeaconClient::generalized_indices(initial_sync.header.slot)
+			.current_sync_committee;
+
+		assert!(EthereumBeaconClient::check_sync_committee_proof(
+			&initial_sync.current_sync_committee,
+			&initial_sync.current_sync_committee_branch,
+			initial_sync.header.state_root,
+			gindex.depth,
+			gindex.index,
+		));
+
+		let mut tampered_branch = initial_sync.current_sync_committee_branch.clone();
+		tampered_branch[0] = H256::repeat_byte(0xff);
+		assert!(!EthereumBeaconClient::check_sync_committee_proof(
+			&initial_sync.current_sync_committee,
+			&tampered_branch,
+			initial_sync.header.state_root,
+			gindex.depth,
+			gindex.index,
+		));
+	});
+}
+
+#[test]
+pub fn test_finalized_count_in_period() {
+	new_tester().execute_with(|| {
+		let period_length = crate::slots_per_period();
+		let slots = [1, 64, 128, period_length + 32];
+
+		for slot in slots {
+			let header = BeaconBlockHeader { slot: Slot(slot), ..Default::default() };
+			let block_root: H256 = merklization::hash_tree_root_beacon_header(header.clone()).unwrap().into();
+			EthereumBeaconClient::store_header(block_root, header);
+		}
+
+		assert_eq!(EthereumBeaconClient::finalized_count_in_period(0), 3);
+		assert_eq!(EthereumBeaconClient::finalized_count_in_period(1), 1);
+		assert_eq!(EthereumBeaconClient::finalized_count_in_period(2), 0);
+	});
+}
+
+fn setup_finalized_header_update_reuse(link_parent: bool, cache_signing_root: bool) -> crate::FinalizedHeaderUpdate {
+	let update = get_finalized_header_update();
+	let current_period = EthereumBeaconClient::compute_current_sync_period(update.attested_header.slot);
+
+	SyncCommittees::<Test>::insert(current_period, get_current_sync_committee_for_finalized_header_update());
+	ChainGenesis::<Test>::set(Genesis{
+		validators_root: hex!("99b09fcd43e5905236c370f184056bec6e6638cfc31a323b304fc4aa789cb4ad").into(),
+	});
+
+	if link_parent {
+		let parent = BeaconBlockHeader { slot: Slot(update.finalized_header.slot.0 - 1), ..Default::default() };
+		<FinalizedHeaders<Test>>::insert(update.finalized_header.parent_root, parent);
+	}
+	if cache_signing_root {
+		let signing_root = EthereumBeaconClient::sync_committee_signing_root(
+			update.attested_header.clone(),
+			update.fork_version,
+		).unwrap();
+		crate::VerifiedSigningRoots::<Test>::put(vec![signing_root]);
+	}
+
+	// Only the fast path can accept this update.
+	let mut tampered = update;
+	tampered.sync_aggregate.sync_committee_signature[0] ^= 0xff;
+	tampered
+}
+
+#[test]
+pub fn test_reuse_verified_signing_root() {
+	new_tester().execute_with(|| {
+		ReuseVerifiedSigningRoots::set(&true);
+		let update = setup_finalized_header_update_reuse(true, true);
+
+		assert_ok!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()));
+		assert!(MockBls::calls().is_empty());
+		assert!(<FinalizedHeadersBySlot<Test>>::contains_key(update.finalized_header.slot));
+	});
+}
+
+#[test]
+pub fn test_reuse_verified_signing_root_falls_back_to_full_verification() {
+	// Disabled
+	new_tester().execute_with(|| {
+		let update = setup_finalized_header_update_reuse(true, true);
+		assert!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update).is_err());
+	});
+
+	// Finalized header doesn't link to a stored header
+	new_tester().execute_with(|| {
+		ReuseVerifiedSigningRoots::set(&true);
+		let update = setup_finalized_header_update_reuse(false, true);
+		assert!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update).is_err());
+	});
+
+	// Signing root not verified before, but recorded once a valid update is verified in full
+	new_tester().execute_with(|| {
+		ReuseVerifiedSigningRoots::set(&true);
+		let tampered = setup_finalized_header_update_reuse(true, false);
+		assert!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), tampered).is_err());
+
+		let update = get_finalized_header_update();
+		assert_ok!(EthereumBeaconClient::import_finalized_header(Origin::signed(1), update.clone()));
+		let signing_root = EthereumBeaconClient::sync_committee_signing_root(
+			update.attested_header,
+			update.fork_version,
+		).unwrap();
+		assert_eq!(crate::VerifiedSigningRoots::<Test>::get(), vec![signing_root]);
+	});
+}
+
+#[test]
+pub fn test_store_sync_committee_with_duplicate_pubkeys() {
+	new_tester().execute_with(|| {
+		System::set_block_number(1);
+
+		let sync_committee = get_initial_sync().current_sync_committee;
+		EthereumBeaconClient::store_sync_committee(1, sync_committee.clone());
+		assert!(System::events().is_empty());
+
+		let mut suspicious = sync_committee;
+		suspicious.pubkeys[1] = suspicious.pubkeys[0].clone();
+		EthereumBeaconClient::store_sync_committee(2, suspicious.clone());
+
+		System::assert_last_event(
+			crate::Event::<Test>::SuspiciousCommittee { period: 2, duplicates: 1 }.into()
+		);
+		assert_eq!(<SyncCommittees<Test>>::get(2), suspicious);
+	});
+}
+
+#[test]
+pub fn test_is_finalized() {
+	new_tester().execute_with(|| {
+		let header = BeaconBlockHeader { slot: Slot(64), ..Default::default() };
+		let block_root: H256 = merklization::hash_tree_root_beacon_header(header.clone()).unwrap().into();
+		EthereumBeaconClient::store_header(block_root, header);
+
+		assert!(EthereumBeaconClient::is_finalized(block_root));
+		assert!(!EthereumBeaconClient::is_finalized(H256::repeat_byte(1)));
+	});
+}
@@ -0,0 +1,11 @@
+//! Runtime API for the incentivized outbound channel, used by applications to quote the fee
+//! for a message before submitting it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+sp_api::decl_runtime_apis! {
+	pub trait IncentivizedOutboundChannelApi {
+		/// Fee charged for submitting a message with a payload of `payload_size` bytes.
+		fn estimate_fee(payload_size: u32) -> u128;
+	}
+}
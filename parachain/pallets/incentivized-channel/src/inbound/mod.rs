@@ -8,13 +8,19 @@ pub mod weights;
 mod test;
 
 use frame_support::{
+	dispatch::DispatchResult,
 	log,
 	traits::{
 		Currency, EnsureOrigin, ExistenceRequirement::KeepAlive, Get, Imbalance, WithdrawReasons,
 	},
+	transactional,
 };
 use frame_system::ensure_signed;
-use snowbridge_core::{ChannelId, Message, MessageDispatch, MessageId, Verifier};
+use snowbridge_core::{
+	BeaconProof, BeaconVerifier, ChannelId, Message, MessageDispatch, MessageId,
+	RewardHeaderImport, Verifier,
+};
+use snowbridge_ethereum::Log;
 use sp_core::{H160, U256};
 use sp_std::convert::TryFrom;
 
@@ -47,6 +53,9 @@ pub mod pallet {
 		/// Verifier module for message verification.
 		type Verifier: Verifier;
 
+		/// Verifier module for messages proven against finalized beacon chain headers.
+		type BeaconVerifier: BeaconVerifier;
+
 		/// Verifier module for message verification.
 		type MessageDispatch: MessageDispatch<Self, MessageId>;
 
@@ -62,6 +71,10 @@ pub mod pallet {
 
 		type FeeConverter: Convert<U256, Option<BalanceOf<Self>>>;
 
+		/// Amount paid from the treasury account to a relayer for each imported beacon header
+		#[pallet::constant]
+		type HeaderImportReward: Get<BalanceOf<Self>>;
+
 		/// The origin which may update reward related params
 		type UpdateOrigin: EnsureOrigin<Self::Origin>;
 
@@ -83,6 +96,8 @@ pub mod pallet {
 		InvalidEnvelope,
 		/// Message has an unexpected nonce.
 		InvalidNonce,
+		/// Computed relayer reward is larger than the fee it is paid from.
+		RewardExceedsFee,
 	}
 
 	/// Source channel on the ethereum side
@@ -122,11 +137,43 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::weight(100_000_000)]
+		#[transactional]
 		pub fn submit(origin: OriginFor<T>, message: Message) -> DispatchResult {
 			let relayer = ensure_signed(origin)?;
 			// submit message to verifier for verification
 			let log = T::Verifier::verify(&message)?;
+			Self::process_log(log, &relayer)
+		}
 
+		#[pallet::weight(100_000_000)]
+		#[transactional]
+		pub fn submit_with_beacon_proof(
+			origin: OriginFor<T>,
+			message: Message,
+			beacon_proof: BeaconProof,
+		) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			// verify the message against a finalized beacon chain header
+			let log = T::BeaconVerifier::verify(&message, &beacon_proof)?;
+			Self::process_log(log, &relayer)
+		}
+
+		#[pallet::weight(T::WeightInfo::set_reward_fraction())]
+		pub fn set_reward_fraction(origin: OriginFor<T>, fraction: Perbill) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			<RewardFraction<T>>::set(fraction);
+			Ok(())
+		}
+	}
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type PositiveImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::PositiveImbalance;
+
+	impl<T: Config> Pallet<T> {
+		fn process_log(log: Log, relayer: &T::AccountId) -> DispatchResult {
 			// Decode log into an Envelope
 			let envelope: Envelope<T> =
 				Envelope::try_from(log).map_err(|_| Error::<T>::InvalidEnvelope)?;
@@ -147,7 +194,7 @@ pub mod pallet {
 				}
 			})?;
 
-			Self::handle_fee(envelope.fee, &relayer);
+			Self::handle_fee(envelope.fee, relayer)?;
 
 			let message_id = MessageId::new(ChannelId::Incentivized, envelope.nonce);
 			T::MessageDispatch::dispatch(envelope.source, message_id, &envelope.payload);
@@ -155,37 +202,25 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(T::WeightInfo::set_reward_fraction())]
-		pub fn set_reward_fraction(origin: OriginFor<T>, fraction: Perbill) -> DispatchResult {
-			T::UpdateOrigin::ensure_origin(origin)?;
-			<RewardFraction<T>>::set(fraction);
-			Ok(())
-		}
-	}
-
-	pub type BalanceOf<T> =
-		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-	pub type PositiveImbalanceOf<T> = <<T as Config>::Currency as Currency<
-		<T as frame_system::Config>::AccountId,
-	>>::PositiveImbalance;
-
-	impl<T: Config> Pallet<T> {
 		/*
 		 * Pay the message submission fee into the relayer and treasury account.
 		 *
 		 * - If the fee is zero, do nothing
+		 * - Figure out the fraction of the fee amount that should be paid to the relayer,
+		 *   failing if it exceeds the fee
 		 * - Otherwise, withdraw the fee amount from the DotApp module account, returning a
 		 *   negative imbalance
-		 * - Figure out the fraction of the fee amount that should be paid to the relayer
 		 * - Pay the relayer if their account exists, returning a positive imbalance.
 		 * - Adjust the negative imbalance by offsetting the amount paid to the relayer
 		 * - Resolve the negative imbalance by depositing it into the treasury account
 		 */
-		pub(super) fn handle_fee(amount: BalanceOf<T>, relayer: &T::AccountId) {
+		pub(super) fn handle_fee(amount: BalanceOf<T>, relayer: &T::AccountId) -> DispatchResult {
 			if amount.is_zero() {
-				return
+				return Ok(())
 			}
 
+			let reward_amount = Self::compute_reward(amount)?;
+
 			let imbalance = match T::Currency::withdraw(
 				&T::SourceAccount::get(),
 				amount,
@@ -195,13 +230,10 @@ pub mod pallet {
 				Ok(imbalance) => imbalance,
 				Err(err) => {
 					log::error!("Unable to withdraw from source account: {:?}", err);
-					return
+					return Ok(())
 				},
 			};
 
-			let reward_fraction: Perbill = <RewardFraction<T>>::get();
-			let reward_amount = reward_fraction.mul_ceil(amount);
-
 			let rewarded = T::Currency::deposit_into_existing(relayer, reward_amount)
 				.unwrap_or_else(|_| PositiveImbalanceOf::<T>::zero());
 
@@ -209,11 +241,39 @@ pub mod pallet {
 				Ok(imbalance) => imbalance,
 				Err(_) => {
 					log::error!("Unable to offset imbalance");
-					return
+					return Ok(())
 				},
 			};
 
 			T::Currency::resolve_creating(&T::TreasuryAccount::get(), adjusted_imbalance);
+
+			Ok(())
+		}
+
+		/// Relayer's share of `fee`, according to the current reward fraction.
+		pub(super) fn compute_reward(fee: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+			let reward_fraction: Perbill = <RewardFraction<T>>::get();
+			let reward = reward_fraction.mul_ceil(fee);
+			ensure!(reward <= fee, Error::<T>::RewardExceedsFee);
+
+			Ok(reward)
+		}
+	}
+
+	/// Header import rewards are paid from the treasury account, which collects the share of
+	/// message fees not paid to message relayers.
+	impl<T: Config> RewardHeaderImport<T::AccountId> for Pallet<T> {
+		fn reward(relayer: &T::AccountId) {
+			let amount = T::HeaderImportReward::get();
+			if amount.is_zero() {
+				return
+			}
+
+			if let Err(err) =
+				T::Currency::transfer(&T::TreasuryAccount::get(), relayer, amount, KeepAlive)
+			{
+				log::error!("Unable to pay header import reward: {:?}", err);
+			}
 		}
 	}
 }
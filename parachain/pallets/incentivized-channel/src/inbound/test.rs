@@ -15,7 +15,7 @@ use sp_runtime::{
 };
 use sp_std::{convert::From, marker::PhantomData};
 
-use snowbridge_core::{Message, MessageDispatch, Proof};
+use snowbridge_core::{BeaconProof, Message, MessageDispatch, Proof};
 use snowbridge_ethereum::{Header as EthereumHeader, Log, U256};
 
 use hex_literal::hex;
@@ -108,6 +108,19 @@ impl Verifier for MockVerifier {
 	}
 }
 
+// Mock beacon verifier, accepting any proof with a non-empty execution branch
+pub struct MockBeaconVerifier;
+
+impl BeaconVerifier for MockBeaconVerifier {
+	fn verify(message: &Message, beacon_proof: &BeaconProof) -> Result<Log, DispatchError> {
+		if beacon_proof.execution_branch.is_empty() {
+			return Err(DispatchError::Other("Invalid proof"))
+		}
+		let log: Log = rlp::decode(&message.data).unwrap();
+		Ok(log)
+	}
+}
+
 // Mock Dispatch
 pub struct MockMessageDispatch;
 
@@ -123,6 +136,7 @@ impl MessageDispatch<Test, MessageId> for MockMessageDispatch {
 parameter_types! {
 	pub SourceAccount: AccountId = Keyring::Eve.into();
 	pub TreasuryAccount: AccountId = Keyring::Dave.into();
+	pub const HeaderImportReward: u128 = 1000;
 }
 
 pub struct FeeConverter<T: Config>(PhantomData<T>);
@@ -136,11 +150,13 @@ impl<T: Config> Convert<U256, Option<BalanceOf<T>>> for FeeConverter<T> {
 impl incentivized_inbound_channel::Config for Test {
 	type Event = Event;
 	type Verifier = MockVerifier;
+	type BeaconVerifier = MockBeaconVerifier;
 	type MessageDispatch = MockMessageDispatch;
 	type Currency = Balances;
 	type SourceAccount = SourceAccount;
 	type TreasuryAccount = TreasuryAccount;
 	type FeeConverter = FeeConverter<Self>;
+	type HeaderImportReward = HeaderImportReward;
 	type UpdateOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type WeightInfo = ();
 }
@@ -282,6 +298,50 @@ fn test_submit_with_invalid_nonce() {
 	});
 }
 
+#[test]
+fn test_submit_with_beacon_proof() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer);
+
+		let message = Message {
+			data: MESSAGE_DATA_0.into(),
+			proof: Proof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			},
+		};
+		let beacon_proof = BeaconProof {
+			slot: 1,
+			execution_header: Default::default(),
+			execution_branch: vec![H256::repeat_byte(1)],
+		};
+
+		assert_noop!(
+			IncentivizedInboundChannel::submit_with_beacon_proof(
+				origin.clone(),
+				message.clone(),
+				BeaconProof { execution_branch: vec![], ..beacon_proof.clone() }
+			),
+			DispatchError::Other("Invalid proof")
+		);
+
+		assert_ok!(IncentivizedInboundChannel::submit_with_beacon_proof(
+			origin.clone(),
+			message.clone(),
+			beacon_proof.clone()
+		));
+		assert_eq!(<Nonce<Test>>::get(), 1);
+
+		// Nonces are shared with messages verified by the execution light client
+		assert_noop!(
+			IncentivizedInboundChannel::submit(origin.clone(), message.clone()),
+			Error::<Test>::InvalidNonce
+		);
+	});
+}
+
 #[test]
 fn test_handle_fee() {
 	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
@@ -293,12 +353,42 @@ fn test_handle_fee() {
 
 		let fee = 10000000000; // 1 DOT
 
-		IncentivizedInboundChannel::handle_fee(fee, &relayer);
+		assert_ok!(IncentivizedInboundChannel::handle_fee(fee, &relayer));
 		assert_eq!(Balances::free_balance(&TreasuryAccount::get()), 2000000001);
 		assert_eq!(Balances::free_balance(&relayer), 8000000001);
 	});
 }
 
+#[test]
+fn test_header_import_reward_is_paid_from_treasury() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+
+		let _ = Balances::deposit_creating(&TreasuryAccount::get(), 10000);
+		let _ = Balances::deposit_creating(&relayer, Balances::minimum_balance());
+
+		<IncentivizedInboundChannel as RewardHeaderImport<AccountId>>::reward(&relayer);
+
+		assert_eq!(Balances::free_balance(&TreasuryAccount::get()), 9000);
+		assert_eq!(Balances::free_balance(&relayer), 1001);
+	});
+}
+
+#[test]
+fn test_compute_reward_is_bounded_by_fee() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let fee = 10000000000; // 1 DOT
+
+		for (percent, expected) in [(0, 0), (50, 5000000000), (100, fee)] {
+			<RewardFraction<Test>>::set(Perbill::from_percent(percent));
+
+			let reward = IncentivizedInboundChannel::compute_reward(fee).unwrap();
+			assert_eq!(reward, expected);
+			assert!(reward <= fee);
+		}
+	});
+}
+
 #[test]
 fn test_set_reward_fraction_not_authorized() {
 	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
@@ -70,7 +70,7 @@ pub mod pallet {
 
 		/// Max bytes in a message payload
 		#[pallet::constant]
-		type MaxMessagePayloadSize: Get<u64>;
+		type MaxMessagePayloadSize: Get<u32>;
 
 		/// Max number of messages per commitment
 		#[pallet::constant]
@@ -189,7 +189,7 @@ pub mod pallet {
 			}
 
 			// Attempt to charge a fee for message submission
-			let fee = Self::fee();
+			let fee = Self::estimate_fee(payload.len() as u32);
 			T::FeeCurrency::burn_from(who, fee).map_err(|_| Error::<T>::NoFunds)?;
 
 			<MessageQueue<T>>::try_append(Message {
@@ -206,6 +206,12 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Fee charged by [`Self::submit`] for a message with a payload of `payload_size` bytes.
+		/// The fee is currently flat, regardless of payload size.
+		pub fn estimate_fee(_payload_size: u32) -> u128 {
+			Self::fee()
+		}
+
 		fn commit() -> Weight {
 			let messages: BoundedVec<Message, T::MaxMessagesPerCommit> = <MessageQueue<T>>::take();
 			if messages.is_empty() {
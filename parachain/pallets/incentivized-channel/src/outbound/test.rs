@@ -116,7 +116,7 @@ impl pallet_assets::Config for Test {
 }
 
 parameter_types! {
-	pub const MaxMessagePayloadSize: u64 = 128;
+	pub const MaxMessagePayloadSize: u32 = 128;
 	pub const MaxMessagesPerCommit: u32 = 5;
 }
 
@@ -198,6 +198,22 @@ fn test_submit_fees_burned() {
 	})
 }
 
+#[test]
+fn test_estimate_fee_matches_fee_charged() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+		let payload = vec![0u8; 64];
+
+		Ether::mint_into(&who, 300).unwrap();
+		let estimate = IncentivizedOutboundChannel::estimate_fee(payload.len() as u32);
+
+		assert_ok!(IncentivizedOutboundChannel::submit(&who, target, &payload));
+
+		assert_eq!(Ether::balance(&who), 300 - estimate);
+	})
+}
+
 #[test]
 fn test_submit_not_enough_funds() {
 	new_tester().execute_with(|| {
@@ -15,7 +15,7 @@ use sp_std::prelude::*;
 pub mod assets;
 pub mod types;
 
-pub use types::{ChannelId, Message, MessageId, MessageNonce, Proof};
+pub use types::{BeaconProof, ChannelId, Message, MessageId, MessageNonce, Proof};
 
 /// A trait for verifying messages.
 ///
@@ -30,6 +30,28 @@ pub trait Verifier {
 	) -> Result<(), &'static str>;
 }
 
+/// A trait for verifying messages against execution blocks committed to by finalized beacon
+/// chain blocks.
+pub trait BeaconVerifier {
+	fn verify(message: &Message, beacon_proof: &BeaconProof) -> Result<Log, DispatchError>;
+}
+
+impl BeaconVerifier for () {
+	fn verify(_message: &Message, _beacon_proof: &BeaconProof) -> Result<Log, DispatchError> {
+		Err(DispatchError::Other("Beacon chain verification is not supported"))
+	}
+}
+
+/// Rewards relayers for importing beacon headers, which keeps the light client fresh enough
+/// for inbound messages to be verified.
+pub trait RewardHeaderImport<AccountId> {
+	fn reward(relayer: &AccountId);
+}
+
+impl<AccountId> RewardHeaderImport<AccountId> for () {
+	fn reward(_relayer: &AccountId) {}
+}
+
 /// Outbound submission for applications
 pub trait OutboundRouter<AccountId> {
 	fn submit(
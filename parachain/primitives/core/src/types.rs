@@ -7,6 +7,8 @@ use sp_core::H256;
 use sp_runtime::DigestItem;
 use sp_std::vec::Vec;
 
+use snowbridge_ethereum::Header;
+
 #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct MessageId {
 	pub channel_id: ChannelId,
@@ -50,6 +52,17 @@ pub struct Proof {
 	pub data: (Vec<Vec<u8>>, Vec<Vec<u8>>),
 }
 
+/// Links the execution block a message's receipt is included in to a finalized beacon block.
+#[derive(PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct BeaconProof {
+	// Slot of the finalized beacon block whose execution payload is the message's block.
+	pub slot: u64,
+	// Header of the execution block in which the receipt was included.
+	pub execution_header: Header,
+	// Merkle branch proving the execution block hash in the beacon block body.
+	pub execution_branch: Vec<H256>,
+}
+
 /// Auxiliary [`DigestItem`] to include in header digest.
 #[derive(Encode, Decode, Copy, Clone, PartialEq, RuntimeDebug, TypeInfo)]
 pub enum AuxiliaryDigestItem {
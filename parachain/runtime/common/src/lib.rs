@@ -2,6 +2,7 @@
 
 use frame_support::{dispatch::DispatchResult, parameter_types, PalletId};
 use sp_core::H160;
+use sp_runtime::Perbill;
 use sp_std::marker::PhantomData;
 
 use snowbridge_core::ChannelId;
@@ -29,8 +30,9 @@ where
 }
 
 parameter_types! {
-	pub const MaxMessagePayloadSize: u64 = 256;
+	pub const MaxMessagePayloadSize: u32 = 256;
 	pub const MaxMessagesPerCommit: u32 = 20;
+	pub const CommitThreshold: Perbill = Perbill::from_percent(80);
 }
 
 parameter_types! {
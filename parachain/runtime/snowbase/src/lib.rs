@@ -8,7 +8,7 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use sp_api::impl_runtime_apis;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata, U256};
+use sp_core::{crypto::KeyTypeId, OpaqueMetadata, H256, U256};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, Convert, Keccak256},
@@ -37,7 +37,7 @@ pub use frame_support::{
 	},
 	PalletId, StorageValue,
 };
-use frame_system::EnsureRoot;
+use frame_system::{EnsureRoot, EnsureSigned};
 use pallet_transaction_payment::FeeDetails;
 use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
 pub use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -67,8 +67,8 @@ use snowbridge_xcm_support::XcmAssetTransferer;
 use xcm_executor::{traits::JustTry, Config, XcmExecutor};
 
 use runtime_common::{
-	DotPalletId, MaxMessagePayloadSize, MaxMessagesPerCommit, OutboundRouter, TreasuryPalletId,
-	INDEXING_PREFIX,
+	CommitThreshold, DotPalletId, MaxMessagePayloadSize, MaxMessagesPerCommit, OutboundRouter,
+	TreasuryPalletId, INDEXING_PREFIX,
 };
 
 pub use runtime_primitives::{AccountId, Address, Balance, BlockNumber, Hash, Index, Signature};
@@ -560,16 +560,30 @@ use incentivized_channel::{
 impl basic_channel_inbound::Config for Runtime {
 	type Event = Event;
 	type Verifier = ethereum_light_client::Pallet<Runtime>;
+	type BeaconVerifier = ethereum_beacon_client::Pallet<Runtime>;
 	type MessageDispatch = dispatch::Pallet<Runtime>;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	// Disabled: each heartbeat advances the nonce, so relayers must deliver them too.
+	pub const HeartbeatInterval: BlockNumber = 0;
+	// Disabled: apps may legitimately send identical messages, e.g. two equal transfers.
+	pub const DeduplicateMessages: bool = false;
+	// Enough for relayers to recover a day of missed digests, committing every block.
+	pub const CommitmentHistorySize: u64 = 14_400;
+}
+
 impl basic_channel_outbound::Config for Runtime {
 	const INDEXING_PREFIX: &'static [u8] = INDEXING_PREFIX;
 	type Event = Event;
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type CommitThreshold = CommitThreshold;
+	type HeartbeatInterval = HeartbeatInterval;
+	type DeduplicateMessages = DeduplicateMessages;
+	type CommitmentHistorySize = CommitmentHistorySize;
 	type SetPrincipalOrigin = EnsureRootOrHalfLocalCouncil;
 	type WeightInfo = basic_channel::outbound::weights::SnowbridgeWeight<Self>;
 }
@@ -577,6 +591,8 @@ impl basic_channel_outbound::Config for Runtime {
 parameter_types! {
 	pub SourceAccount: AccountId = DotPalletId::get().into_account();
 	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account();
+	// 0.01 DOT
+	pub const HeaderImportReward: Balance = 100_000_000;
 }
 
 pub struct FeeConverter;
@@ -589,11 +605,13 @@ impl Convert<U256, Option<Balance>> for FeeConverter {
 impl incentivized_channel_inbound::Config for Runtime {
 	type Event = Event;
 	type Verifier = ethereum_light_client::Pallet<Runtime>;
+	type BeaconVerifier = ethereum_beacon_client::Pallet<Runtime>;
 	type MessageDispatch = dispatch::Pallet<Runtime>;
 	type Currency = Balances;
 	type SourceAccount = SourceAccount;
 	type TreasuryAccount = TreasuryAccount;
 	type FeeConverter = FeeConverter;
+	type HeaderImportReward = HeaderImportReward;
 	type UpdateOrigin = EnsureRootOrHalfLocalCouncil;
 	type WeightInfo = incentivized_channel::inbound::weights::SnowbridgeWeight<Self>;
 }
@@ -625,13 +643,60 @@ impl ethereum_light_client::Config for Runtime {
 	type WeightInfo = ethereum_light_client::weights::SnowbridgeWeight<Self>;
 }
 
+parameter_types! {
+	pub const VerifyParentLinkage: bool = false;
+	pub const RequireEpochAlignedFinalizedSlot: bool = false;
+	pub const ReuseVerifiedSigningRoots: bool = false;
+	pub const ElectraForkEpoch: u64 = u64::MAX;
+	// Prater
+	pub const DenebForkEpoch: u64 = 231_680;
+	pub const MaxProofBranchLength: u32 = 16;
+	pub const MaxFailedVerifications: u32 = 100;
+	pub const MaxProposerIndex: u64 = 4_000_000;
+	// Prater
+	pub const GenesisForkVersion: [u8; 4] = [0x00, 0x00, 0x10, 0x20];
+	pub const DenebForkVersion: [u8; 4] = [0x04, 0x00, 0x10, 0x20];
+	pub const ElectraForkVersion: [u8; 4] = [0x05, 0x00, 0x10, 0x20];
+	pub const GenesisTime: u64 = 1616508000;
+	pub const SecondsPerSlot: u64 = 12;
+	// One epoch
+	pub const MaxFutureSlots: u64 = 32;
+	// One epoch
+	pub const PeriodBoundarySlots: u64 = 32;
+	// ~1 day, well within the weak subjectivity period
+	pub const FallingBehindThreshold: u64 = 7200;
+}
+
 impl ethereum_beacon_client::Config for Runtime {
 	type Event = Event;
+	type VerifyParentLinkage = VerifyParentLinkage;
+	type RequireEpochAlignedFinalizedSlot = RequireEpochAlignedFinalizedSlot;
+	type ReuseVerifiedSigningRoots = ReuseVerifiedSigningRoots;
+	type ElectraForkEpoch = ElectraForkEpoch;
+	type DenebForkEpoch = DenebForkEpoch;
+	type GenesisForkVersion = GenesisForkVersion;
+	type DenebForkVersion = DenebForkVersion;
+	type ElectraForkVersion = ElectraForkVersion;
+	type TimeProvider = Timestamp;
+	type FallingBehindThreshold = FallingBehindThreshold;
+	type MaxProposerIndex = MaxProposerIndex;
+	type MaxProofBranchLength = MaxProofBranchLength;
+	type MaxFailedVerifications = MaxFailedVerifications;
+	type GenesisTime = GenesisTime;
+	type SecondsPerSlot = SecondsPerSlot;
+	type MaxFutureSlots = MaxFutureSlots;
+	type PeriodBoundarySlots = PeriodBoundarySlots;
+	type BlsVerifier = ethereum_beacon_client::MilagroBls;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type HeaderSubmissionOrigin = EnsureSigned<AccountId>;
+	type RelayerRewards = incentivized_channel_inbound::Pallet<Runtime>;
 }
 
 parameter_types! {
 	pub const EtherAssetId: u128 = 0;
 	pub const EtherAppPalletId: PalletId = PalletId(*b"etherapp");
+	pub const EtherDecimalsConversion: eth_app::DecimalsConversion =
+		eth_app::DecimalsConversion::IDENTITY;
 }
 
 impl eth_app::Config for Runtime {
@@ -642,6 +707,7 @@ impl eth_app::Config for Runtime {
 	type CallOrigin = EnsureEthereumAccount;
 	type WeightInfo = eth_app::weights::SnowbridgeWeight<Self>;
 	type XcmReserveTransfer = XcmAssetTransferer<Runtime>;
+	type DecimalsConversion = EtherDecimalsConversion;
 }
 
 parameter_types! {
@@ -920,6 +986,96 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl basic_channel_runtime_api::BasicOutboundChannelApi<Block> for Runtime {
+		fn offchain_key(hash: H256) -> Vec<u8> {
+			BasicOutboundChannel::offchain_key(hash)
+		}
+
+		fn commitment_hash_by_nonce(nonce: u64) -> Option<H256> {
+			BasicOutboundChannel::commitment_hash_by_nonce(nonce)
+		}
+
+		fn is_nonce_committed(nonce: u64) -> bool {
+			BasicOutboundChannel::is_nonce_committed(nonce)
+		}
+
+		fn commitment_hash_of_encoded_bundle(encoded: Vec<u8>) -> Option<H256> {
+			BasicOutboundChannel::commitment_hash_of_encoded_bundle(encoded)
+		}
+
+		fn is_offchain_bundle_intact(nonce: u64, encoded: Vec<u8>) -> bool {
+			BasicOutboundChannel::is_offchain_bundle_intact(nonce, encoded)
+		}
+	}
+
+	impl incentivized_channel_runtime_api::IncentivizedOutboundChannelApi<Block> for Runtime {
+		fn estimate_fee(payload_size: u32) -> u128 {
+			IncentivizedOutboundChannel::estimate_fee(payload_size)
+		}
+	}
+
+	impl ethereum_beacon_client_runtime_api::EthereumBeaconClientApi<Block> for Runtime {
+		fn sync_committee_for_slot(slot: u64) -> Option<ethereum_beacon_client::SyncCommittee> {
+			EthereumBeaconClient::sync_committee_for_slot(slot.into())
+		}
+
+		fn header_status(slot: u64) -> ethereum_beacon_client::HeaderStatus {
+			EthereumBeaconClient::header_status(slot.into())
+		}
+
+		fn next_expected_period() -> u64 {
+			EthereumBeaconClient::next_expected_period()
+		}
+
+		fn period_context(slot: u64) -> ethereum_beacon_client::PeriodContext {
+			ethereum_beacon_client::period_context(slot.into())
+		}
+
+		fn current_period() -> Option<u64> {
+			EthereumBeaconClient::current_period()
+		}
+
+		fn next_sync_committee() -> Option<ethereum_beacon_client::SyncCommittee> {
+			EthereumBeaconClient::next_sync_committee()
+		}
+
+		fn slots_since_last_finalized(current_slot: u64) -> Option<u64> {
+			EthereumBeaconClient::slots_since_last_finalized(current_slot)
+		}
+
+		fn is_finalized(block_root: H256) -> bool {
+			EthereumBeaconClient::is_finalized(block_root)
+		}
+
+		fn export_state() -> Option<ethereum_beacon_client::LightClientSnapshot> {
+			EthereumBeaconClient::export_state()
+		}
+
+		fn chain_config() -> ethereum_beacon_client::ChainConfig {
+			EthereumBeaconClient::chain_config()
+		}
+
+		fn sync_committee_root(committee: ethereum_beacon_client::SyncCommittee) -> Option<H256> {
+			EthereumBeaconClient::sync_committee_root(&committee).ok()
+		}
+
+		fn check_sync_committee_proof(
+			committee: ethereum_beacon_client::SyncCommittee,
+			branch: Vec<H256>,
+			state_root: H256,
+			depth: u64,
+			index: u64,
+		) -> bool {
+			EthereumBeaconClient::check_sync_committee_proof(
+				&committee,
+				&branch,
+				state_root,
+				depth,
+				index,
+			)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -8,7 +8,7 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use sp_api::impl_runtime_apis;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata, U256};
+use sp_core::{crypto::KeyTypeId, OpaqueMetadata, H256, U256};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, Convert, Keccak256},
@@ -68,8 +68,8 @@ use snowbridge_xcm_support::XcmAssetTransferer;
 use xcm_executor::{traits::JustTry, Config, XcmExecutor};
 
 use runtime_common::{
-	DotPalletId, MaxMessagePayloadSize, MaxMessagesPerCommit, OutboundRouter, TreasuryPalletId,
-	INDEXING_PREFIX,
+	CommitThreshold, DotPalletId, MaxMessagePayloadSize, MaxMessagesPerCommit, OutboundRouter,
+	TreasuryPalletId, INDEXING_PREFIX,
 };
 
 pub use runtime_primitives::{AccountId, Address, Balance, BlockNumber, Hash, Index, Signature};
@@ -561,16 +561,30 @@ use incentivized_channel::{
 impl basic_channel_inbound::Config for Runtime {
 	type Event = Event;
 	type Verifier = ethereum_light_client::Pallet<Runtime>;
+	type BeaconVerifier = ();
 	type MessageDispatch = dispatch::Pallet<Runtime>;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	// Disabled: each heartbeat advances the nonce, so relayers must deliver them too.
+	pub const HeartbeatInterval: BlockNumber = 0;
+	// Disabled: apps may legitimately send identical messages, e.g. two equal transfers.
+	pub const DeduplicateMessages: bool = false;
+	// Enough for relayers to recover a day of missed digests, committing every block.
+	pub const CommitmentHistorySize: u64 = 14_400;
+}
+
 impl basic_channel_outbound::Config for Runtime {
 	const INDEXING_PREFIX: &'static [u8] = INDEXING_PREFIX;
 	type Event = Event;
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type CommitThreshold = CommitThreshold;
+	type HeartbeatInterval = HeartbeatInterval;
+	type DeduplicateMessages = DeduplicateMessages;
+	type CommitmentHistorySize = CommitmentHistorySize;
 	type SetPrincipalOrigin = EnsureRootOrHalfLocalCouncil;
 	type WeightInfo = basic_channel::outbound::weights::SnowbridgeWeight<Self>;
 }
@@ -578,6 +592,8 @@ impl basic_channel_outbound::Config for Runtime {
 parameter_types! {
 	pub SourceAccount: AccountId = DotPalletId::get().into_account();
 	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account();
+	// 0.01 DOT
+	pub const HeaderImportReward: Balance = 100_000_000;
 }
 
 pub struct FeeConverter;
@@ -590,11 +606,13 @@ impl Convert<U256, Option<Balance>> for FeeConverter {
 impl incentivized_channel_inbound::Config for Runtime {
 	type Event = Event;
 	type Verifier = ethereum_light_client::Pallet<Runtime>;
+	type BeaconVerifier = ();
 	type MessageDispatch = dispatch::Pallet<Runtime>;
 	type Currency = Balances;
 	type SourceAccount = SourceAccount;
 	type TreasuryAccount = TreasuryAccount;
 	type FeeConverter = FeeConverter;
+	type HeaderImportReward = HeaderImportReward;
 	type UpdateOrigin = EnsureRootOrHalfLocalCouncil;
 	type WeightInfo = incentivized_channel::inbound::weights::SnowbridgeWeight<Self>;
 }
@@ -629,6 +647,8 @@ impl ethereum_light_client::Config for Runtime {
 parameter_types! {
 	pub const EtherAssetId: u128 = 0;
 	pub const EtherAppPalletId: PalletId = PalletId(*b"etherapp");
+	pub const EtherDecimalsConversion: eth_app::DecimalsConversion =
+		eth_app::DecimalsConversion::IDENTITY;
 }
 
 impl eth_app::Config for Runtime {
@@ -639,6 +659,7 @@ impl eth_app::Config for Runtime {
 	type CallOrigin = EnsureEthereumAccount;
 	type WeightInfo = eth_app::weights::SnowbridgeWeight<Self>;
 	type XcmReserveTransfer = XcmAssetTransferer<Runtime>;
+	type DecimalsConversion = EtherDecimalsConversion;
 }
 
 parameter_types! {
@@ -916,6 +937,34 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl basic_channel_runtime_api::BasicOutboundChannelApi<Block> for Runtime {
+		fn offchain_key(hash: H256) -> Vec<u8> {
+			BasicOutboundChannel::offchain_key(hash)
+		}
+
+		fn commitment_hash_by_nonce(nonce: u64) -> Option<H256> {
+			BasicOutboundChannel::commitment_hash_by_nonce(nonce)
+		}
+
+		fn is_nonce_committed(nonce: u64) -> bool {
+			BasicOutboundChannel::is_nonce_committed(nonce)
+		}
+
+		fn commitment_hash_of_encoded_bundle(encoded: Vec<u8>) -> Option<H256> {
+			BasicOutboundChannel::commitment_hash_of_encoded_bundle(encoded)
+		}
+
+		fn is_offchain_bundle_intact(nonce: u64, encoded: Vec<u8>) -> bool {
+			BasicOutboundChannel::is_offchain_bundle_intact(nonce, encoded)
+		}
+	}
+
+	impl incentivized_channel_runtime_api::IncentivizedOutboundChannelApi<Block> for Runtime {
+		fn estimate_fee(payload_size: u32) -> u128 {
+			IncentivizedOutboundChannel::estimate_fee(payload_size)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -10,9 +10,10 @@ func (b BasicOutboundChannelMessageBundle) IntoInboundMessageBundle() basic.Basi
 	var messages []basic.BasicInboundChannelMessage
 	for _, m := range b.Messages {
 		messages = append(messages, basic.BasicInboundChannelMessage{
-			Id:      m.ID,
-			Target:  m.Target,
-			Payload: m.Payload,
+			Id:              m.ID,
+			Target:          m.Target,
+			Payload:         m.Payload,
+			CommitmentIndex: m.CommitmentIndex,
 		})
 	}
 	return basic.BasicInboundChannelMessageBundle{
@@ -22,14 +23,16 @@ func (b BasicOutboundChannelMessageBundle) IntoInboundMessageBundle() basic.Basi
 }
 
 type BasicOutboundChannelMessageBundle struct {
+	Version  uint8
 	Nonce    uint64
 	Messages []BasicOutboundChannelMessage
 }
 
 type BasicOutboundChannelMessage struct {
-	ID      uint64
-	Target  [20]byte
-	Payload []byte
+	ID              uint64
+	Target          [20]byte
+	Payload         []byte
+	CommitmentIndex uint32
 }
 
 func (b IncentivizedOutboundChannelMessageBundle) IntoInboundMessageBundle() incentivized.IncentivizedInboundChannelMessageBundle {
@@ -9,10 +9,21 @@ import (
 
 var IndexingPrefix = []byte("commitment")
 
+// BasicCommitmentVersion is the version of the basic outbound channel's offchain key
+// scheme and message bundle format. It must match COMMITMENT_VERSION in the parachain.
+const BasicCommitmentVersion uint8 = 1
+
 func MakeStorageKey(channelID ChannelID, hash types.H256) ([]byte, error) {
 	var buffer = bytes.Buffer{}
 	encoder := scale.NewEncoder(&buffer)
 
+	if channelID.IsBasic {
+		err := encoder.Encode(BasicCommitmentVersion)
+		if err != nil {
+			return nil, err
+		}
+	}
+
 	err := encoder.Encode(IndexingPrefix)
 	if err != nil {
 		return nil, err
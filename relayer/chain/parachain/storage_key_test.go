@@ -25,6 +25,7 @@ func TestMakeStorageKey(t *testing.T) {
 
 	assert.Equal(t,
 		[]byte{
+			1,
 			40, 99, 111, 109, 109, 105, 116, 109, 101, 110, 116, 0,
 			7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7,
 			7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7,
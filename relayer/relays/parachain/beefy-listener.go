@@ -582,9 +582,8 @@ func (li *BeefyListener) scanForCommitments(
 					return nil, err
 				}
 
-				if len(bundle.Messages) == 0 {
-					return nil, fmt.Errorf("bundle has no messages")
-				}
+				// Heartbeat bundles have no messages, but still advance the nonce
+				// and must be delivered like any other bundle.
 
 				// This case will be hit if basicNonceToFind has not yet
 				// been committed yet. Channels emit commitments every N